@@ -0,0 +1,66 @@
+use std::fmt;
+
+/// Answer produced by a single puzzle part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    Multi(Vec<Answer>),
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Answer {
+                    Answer::Int(x as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<u128> for Answer {
+    /// Values above `i128::MAX` become strings rather than wrapping around.
+    fn from(x: u128) -> Answer {
+        i128::try_from(x).map_or_else(|_| Answer::Str(x.to_string()), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(String::from(s))
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(v: Vec<T>) -> Answer {
+        Answer::Multi(v.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(x) => write!(f, "{x}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::Multi(v) => {
+                for (i, a) in v.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{a}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
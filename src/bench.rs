@@ -0,0 +1,99 @@
+use crate::{Answer, Part, Result};
+use std::cell::Cell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static PARSED: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Marks the end of the parsing phase of the part running on this thread.
+pub fn parsed() {
+    PARSED.with(|parsed| parsed.set(Some(Instant::now())));
+}
+
+/// Time spent parsing the input and solving the puzzle in a single run.
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Runs a part once on the puzzle text, splitting its running time at the call to
+/// [`parsed`].
+///
+/// Parts which never call [`parsed`] are accounted entirely as parsing.
+pub fn time(solve: Part, text: &str) -> Result<(Answer, Timing)> {
+    PARSED.with(|parsed| parsed.set(None));
+    let start = Instant::now();
+    let answer = solve(text)?;
+    let end = Instant::now();
+    let parsed = PARSED.with(|parsed| parsed.take()).unwrap_or(end);
+    let timing = Timing {
+        parse: parsed - start,
+        solve: end - parsed,
+    };
+    Ok((answer, timing))
+}
+
+/// Minimum, median and maximum of a series of durations.
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Statistics of a non-empty series of durations.
+    pub fn new(durations: &[Duration]) -> Stats {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            max: sorted[n - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>10} {:>10} {:>10}",
+            format!("{:.2?}", self.min),
+            format!("{:.2?}", self.median),
+            format!("{:.2?}", self.max)
+        )
+    }
+}
+
+/// Timing statistics of a part over several runs.
+pub struct Report {
+    pub answer: Answer,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs a part `runs` times (at least once) and collects statistics of both phases.
+pub fn bench(solve: Part, text: &str, runs: usize) -> Result<Report> {
+    let (answer, timing) = time(solve, text)?;
+    let mut timings = vec![timing];
+    for _ in 1..runs {
+        timings.push(time(solve, text)?.1);
+    }
+    let parse: Vec<_> = timings.iter().map(|t| t.parse).collect();
+    let solve: Vec<_> = timings.iter().map(|t| t.solve).collect();
+    Ok(Report {
+        answer,
+        parse: Stats::new(&parse),
+        solve: Stats::new(&solve),
+    })
+}
@@ -1,59 +1,161 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-
-pub fn part1(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let sum: u32 = reader
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let bytes = line.as_bytes();
-            let first_digit = bytes
-                .iter()
-                .find_map(|&b| (b as char).to_digit(10))
-                .expect("No digit found");
-            let last_digit = bytes
-                .iter()
-                .rev()
-                .find_map(|&b| (b as char).to_digit(10))
-                .expect("No digit found");
-            first_digit * 10u32 + last_digit
-        })
-        .sum();
-    println!("{sum}");
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::Answer;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// The digits `0` to `9`.
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// The English words for the digits 1 to 9.
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Occurrence of a token, with its byte range in the line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+/// Calibration value of a line together with the matches it was made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calibration {
+    pub value: u64,
+    pub first: Match,
+    pub last: Match,
+}
+
+/// Set of tokens standing for digit values, compiled into an Aho-Corasick automaton that
+/// finds all of them, overlapping ones included, in a single pass over a line.
+pub struct Vocabulary {
+    /// Transition of every state on every byte; state 0 is the start.
+    next: Vec<[usize; 256]>,
+    /// Length and value of each token ending in each state.
+    out: Vec<Vec<(usize, u32)>>,
 }
 
-pub fn part2(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let digits = vec![
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-    let find_digit = |s: &str| -> Option<u32> {
-        s.chars().next().and_then(|c| c.to_digit(10)).or_else(|| {
-            digits.iter().enumerate().find_map(|(i, &digit)| {
-                if s.starts_with(digit) {
-                    Some(i as u32 + 1)
+impl Vocabulary {
+    /// Compiles the `(token, value)` pairs; panics if a token is empty.
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> Vocabulary {
+        // Trie of the tokens, where 0 marks a missing child since the start state is
+        // nobody's child.
+        let mut next = vec![[0; 256]];
+        let mut own = vec![None];
+        for (token, value) in tokens {
+            assert!(!token.is_empty(), "Empty token");
+            let mut s = 0;
+            for &b in token.as_bytes() {
+                if next[s][b as usize] == 0 {
+                    next[s][b as usize] = next.len();
+                    next.push([0; 256]);
+                    own.push(None);
+                }
+                s = next[s][b as usize];
+            }
+            own[s] = Some((token.len(), value));
+        }
+        // Breadth-first over the trie, pointing missing transitions to where the
+        // longest proper suffix that is still a token prefix would go.
+        let mut fail = vec![0; next.len()];
+        let mut out = vec![vec![]; next.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(s) = queue.pop_front() {
+            out[s] = own[s].into_iter().chain(out[fail[s]].clone()).collect();
+            #[allow(clippy::needless_range_loop)]
+            for b in 0..256 {
+                let t = next[s][b];
+                let suffix = if s == 0 { 0 } else { next[fail[s]][b] };
+                if t == 0 {
+                    next[s][b] = suffix;
                 } else {
-                    None
+                    fail[t] = suffix;
+                    queue.push_back(t);
                 }
+            }
+        }
+        Vocabulary { next, out }
+    }
+
+    /// All token occurrences in `line`, in order of their end.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(0, |s, (i, b)| {
+                *s = self.next[*s][b as usize];
+                Some((i + 1, *s))
             })
+            .flat_map(|(end, s)| {
+                self.out[s].iter().map(move |&(len, value)| Match {
+                    start: end - len,
+                    end,
+                    value,
+                })
+            })
+    }
+
+    /// Value made of the first and the last token of `line` (ordered by where they start,
+    /// preferring longer tokens), or `None` if it has none.
+    pub fn calibrate(&self, line: &str) -> Option<Calibration> {
+        let (mut first, mut last): (Option<Match>, Option<Match>) = (None, None);
+        for m in self.matches(line) {
+            if first.is_none_or(|f| (m.start, Reverse(m.end)) < (f.start, Reverse(f.end))) {
+                first = Some(m);
+            }
+            if last.is_none_or(|l| (m.start, m.end) > (l.start, l.end)) {
+                last = Some(m);
+            }
+        }
+        let (first, last) = first.zip(last)?;
+        Some(Calibration {
+            value: first.value as u64 * 10 + last.value as u64,
+            first,
+            last,
         })
-    };
-    let sum: u32 = reader
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let first_digit = (0..line.len())
-                .find_map(|i| find_digit(&line[i..]))
-                .expect("No digit found");
-            let last_digit = (0..line.len())
-                .rev()
-                .find_map(|i| find_digit(&line[i..]))
-                .expect("No digit found");
-            first_digit * 10u32 + last_digit
-        })
-        .sum();
-    println!("{sum}");
+    }
+}
+
+/// Sums the calibration values of all lines.
+pub fn calibration_sum(text: &str, vocabulary: &Vocabulary) -> Result<u64> {
+    let mut sum = 0;
+    for (i, line) in text.lines().enumerate() {
+        match vocabulary.calibrate(line) {
+            Some(calibration) => sum += calibration.value,
+            None => return Err(Error::at(line, line, "No digit found")).line(i + 1),
+        }
+    }
+    Ok(sum)
+}
+
+pub fn part1(text: &str) -> Result<Answer> {
+    let vocabulary = Vocabulary::new(DIGITS);
+    bench::parsed();
+    Ok(calibration_sum(text, &vocabulary)?.into())
+}
+
+pub fn part2(text: &str) -> Result<Answer> {
+    let vocabulary = Vocabulary::new(DIGITS.into_iter().chain(ENGLISH));
+    bench::parsed();
+    Ok(calibration_sum(text, &vocabulary)?.into())
 }
@@ -1,76 +1,150 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
+use std::collections::BTreeMap;
 
-type RGB = [u32; 3];
+/// Colors of the cubes in the puzzle.
+pub const COLORS: [&str; 3] = ["red", "green", "blue"];
 
-struct Game {
-    game_id: u32,
-    subsets: Vec<RGB>,
-}
+/// Cube counts by color name; colors that are not listed count as zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag(pub BTreeMap<String, u32>);
 
-impl Game {
-    fn parse(line: &str) -> Game {
-        assert!(line.starts_with("Game "));
-        let colon = line.find(':').unwrap();
-        Game {
-            game_id: line[5..colon].parse().unwrap(),
-            subsets: line[colon + 2..].split("; ").map(Game::parse_rgb).collect(),
-        }
+impl Bag {
+    pub fn new() -> Bag {
+        Bag::default()
     }
 
-    fn parse_rgb(s: &str) -> RGB {
-        let mut out: RGB = [0, 0, 0];
-        for color in s.split(", ") {
-            let space = color.find(' ').unwrap();
-            let idx: usize = match &color[space + 1..] {
-                "red" => 0,
-                "green" => 1,
-                "blue" => 2,
-                _ => panic!("Invalid color!"),
-            };
-            out[idx] = color[0..space].parse().unwrap();
+    /// Parses a list of cube counts such as `3 blue, 4 red`, which must be a subslice
+    /// of `line`.
+    pub fn parse(line: &str, s: &str) -> Result<Bag> {
+        let mut out = Bag::new();
+        for cubes in s.split(", ") {
+            let (count, color) = input::split_once(line, cubes, " ")?;
+            if color.is_empty() {
+                return Err(Error::at(line, cubes, "Expected a color"));
+            }
+            out.0
+                .insert(String::from(color), input::parse(line, count)?);
         }
-        out
+        Ok(out)
     }
 
-    fn max(&self) -> RGB {
-        let mut out: RGB = [0, 0, 0];
-        for rgb in self.subsets.iter() {
-            out = [out[0].max(rgb[0]), out[1].max(rgb[1]), out[2].max(rgb[2])];
+    /// Number of cubes of `color`.
+    pub fn get(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    /// Smallest bag holding the cubes of both bags, one at a time.
+    pub fn max(&self, other: &Bag) -> Bag {
+        let mut out = self.clone();
+        for (color, &count) in other.0.iter() {
+            let c = out.0.entry(color.clone()).or_insert(0);
+            *c = (*c).max(count);
         }
         out
     }
 
-    fn valid(&self) -> bool {
-        let max = self.max();
-        max[0] <= 12 && max[1] <= 13 && max[2] <= 14
+    /// Colors of which `cubes` has more than this bag.
+    pub fn lacking<'a>(&self, cubes: &'a Bag) -> Vec<&'a str> {
+        cubes
+            .0
+            .iter()
+            .filter(|&(color, &count)| count > self.get(color))
+            .map(|(color, _)| color.as_str())
+            .collect()
+    }
+
+    /// Whether the bag holds at least the given cubes.
+    pub fn admits(&self, cubes: &Bag) -> bool {
+        self.lacking(cubes).is_empty()
+    }
+
+    /// Product of the numbers of cubes of the given colors.
+    pub fn power<'a>(&self, colors: impl IntoIterator<Item = &'a str>) -> u64 {
+        colors.into_iter().map(|c| self.get(c) as u64).product()
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(iter: I) -> Bag {
+        Bag(iter
+            .into_iter()
+            .map(|(color, count)| (String::from(color), count))
+            .collect())
+    }
+}
+
+/// A single game with the revealed subsets of cubes.
+pub struct Game {
+    pub game_id: u32,
+    pub subsets: Vec<Bag>,
+}
+
+impl Game {
+    /// Parses a line such as `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    pub fn parse(line: &str) -> Result<Game> {
+        let (game_id, subsets) = input::record(line, "Game")?;
+        Ok(Game {
+            game_id,
+            subsets: subsets
+                .split("; ")
+                .map(|s| Bag::parse(line, s))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Smallest bag the game is possible with.
+    pub fn min_bag(&self) -> Bag {
+        self.subsets.iter().fold(Bag::new(), |acc, s| acc.max(s))
+    }
+
+    /// Whether the game is possible with the cubes in `bag`.
+    pub fn possible(&self, bag: &Bag) -> bool {
+        bag.admits(&self.min_bag())
     }
 
-    fn power(&self) -> u32 {
-        let max = self.max();
-        max[0] * max[1] * max[2]
+    /// Colors of which `bag` has too few cubes for the game.
+    pub fn blocking(&self, bag: &Bag) -> Vec<String> {
+        let min = self.min_bag();
+        bag.lacking(&min).into_iter().map(String::from).collect()
     }
 }
 
-pub fn part1(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let sum: u32 = reader
-        .lines()
-        .map(|line| Game::parse(&line.unwrap()))
-        .filter(|game| game.valid())
+/// Smallest bag all of the games are possible with.
+pub fn min_bag(games: &[Game]) -> Bag {
+    games
+        .iter()
+        .fold(Bag::new(), |acc, g| acc.max(&g.min_bag()))
+}
+
+/// Ids of the games that are impossible with `bag`, by each color there are too few of.
+pub fn blocked(games: &[Game], bag: &Bag) -> BTreeMap<String, Vec<u32>> {
+    let mut out: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    for game in games {
+        for color in game.blocking(bag) {
+            out.entry(color).or_default().push(game.game_id);
+        }
+    }
+    out
+}
+
+pub fn part1(text: &str) -> Result<Answer> {
+    let games = input::parse_lines(text, Game::parse)?;
+    bench::parsed();
+    let bag: Bag = COLORS.into_iter().zip([12, 13, 14]).collect();
+    let sum: u32 = games
+        .iter()
+        .filter(|game| game.possible(&bag))
         .map(|game| game.game_id)
         .sum();
-    println!("{sum}");
+    Ok(sum.into())
 }
 
-pub fn part2(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let sum: u32 = reader
-        .lines()
-        .map(|line| Game::parse(&line.unwrap()))
-        .map(|game| game.power())
-        .sum();
-    println!("{sum}");
+pub fn part2(text: &str) -> Result<Answer> {
+    let games = input::parse_lines(text, Game::parse)?;
+    bench::parsed();
+    let sum: u64 = games.iter().map(|game| game.min_bag().power(COLORS)).sum();
+    Ok(sum.into())
 }
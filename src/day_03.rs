@@ -1,146 +1,163 @@
+use crate::bench;
+use crate::error::Result;
+use crate::grid::{Grid, Pos};
+use crate::Answer;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Number {
-    row: usize,
-    col: usize,
-    len: usize,
-    num: u32,
+/// Part number found in the engine schematic.
+pub struct Number {
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+    pub num: u32,
 }
 
-impl Number {
-    fn surroundings(&self) -> Vec<(i32, i32)> {
-        let mut out = vec![];
-        out.reserve(2 * self.len + 6);
-        let row = self.row as i32;
-        let col = self.col as i32;
-        let len = self.len as i32;
-        for j in col - 1..=col + len {
-            out.push((row - 1, j));
-            out.push((row + 1, j));
-        }
-        out.push((row, col - 1));
-        out.push((row, col + len));
-        out
-    }
+/// Whether the tile is a symbol, that is neither a digit nor `.`.
+pub fn is_symbol(b: u8) -> bool {
+    !b.is_ascii_digit() && b != b'.'
 }
 
-struct Engine {
-    schema: Vec<String>,
-    numbers: Vec<Number>,
-    rows: usize,
-    cols: usize,
+/// Engine schematic together with all numbers found in it.
+pub struct Engine {
+    pub map: Grid<u8>,
+    pub numbers: Vec<Number>,
 }
 
-type StarsHashMap = HashMap<(i32, i32), Vec<u32>>;
+/// Which numbers touch which symbols (including diagonally). A number may touch several
+/// symbols and a symbol several numbers.
+pub struct Adjacency {
+    /// Positions and characters of all symbols, in reading order.
+    pub symbols: Vec<(Pos, u8)>,
+    /// Indices into `symbols` of the symbols touching each number of the engine.
+    pub number_symbols: Vec<Vec<usize>>,
+    /// Indices into the numbers of the engine touching each symbol.
+    pub symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    /// All `(number, symbol)` index pairs that touch.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        self.number_symbols
+            .iter()
+            .enumerate()
+            .flat_map(|(n, symbols)| symbols.iter().map(move |&s| (n, s)))
+            .collect()
+    }
+}
 
 impl Engine {
-    fn new(path: &str) -> Engine {
-        let file = File::open(path).unwrap();
-        let reader = io::BufReader::new(file);
-        let mut engine = Engine {
-            schema: vec![],
-            numbers: vec![],
-            rows: 0,
-            cols: 0,
-        };
-        for line in reader.lines() {
-            let line = line.unwrap();
-            let bytes = line.as_bytes();
-            if engine.cols == 0 {
-                engine.cols = line.len();
-            } else {
-                assert!(engine.cols == line.len());
-            }
-            let mut i: usize = 0;
-            while i < engine.cols {
-                if bytes[i].is_ascii_digit() {
-                    let mut number = Number {
-                        row: engine.rows,
-                        col: i,
-                        len: 0,
-                        num: 0,
-                    };
-                    while i < engine.cols && bytes[i].is_ascii_digit() {
-                        number.len += 1;
-                        number.num = 10 * number.num + (bytes[i] - b'0') as u32;
-                        i += 1;
-                    }
-                    engine.numbers.push(number);
-                } else {
-                    i += 1;
+    /// Parses the engine schematic.
+    pub fn new(text: &str) -> Result<Engine> {
+        let tiles: Vec<u8> = (b'!'..=b'~').collect();
+        let map = Grid::parse(text, &tiles)?;
+        let mut numbers = vec![];
+        for (i, row) in map.rows().enumerate() {
+            let mut j = 0;
+            while j < row.len() {
+                let len = row[j..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if len > 0 {
+                    let num = row[j..j + len]
+                        .iter()
+                        .fold(0, |acc, &b| 10 * acc + (b - b'0') as u32);
+                    numbers.push(Number {
+                        row: i,
+                        col: j,
+                        len,
+                        num,
+                    });
                 }
+                j += len.max(1);
             }
-            engine.rows += 1;
-            engine.schema.push(line);
         }
-        engine
+        Ok(Engine { map, numbers })
     }
 
-    fn get(&self, i: i32, j: i32) -> Option<u8> {
-        if (0..self.rows as i32).contains(&i) && (0..self.cols as i32).contains(&j) {
-            Some(self.schema[i as usize].as_bytes()[j as usize])
-        } else {
-            None
-        }
+    /// Positions of the cells around the number (including diagonally) inside the map.
+    pub fn surroundings<'a>(&'a self, number: &'a Number) -> impl Iterator<Item = Pos> + 'a {
+        let rows = number.row.saturating_sub(1)..=(number.row + 1).min(self.map.height() - 1);
+        let cols =
+            number.col.saturating_sub(1)..=(number.col + number.len).min(self.map.width() - 1);
+        rows.flat_map(move |i| cols.clone().map(move |j| (i, j)))
+            .filter(|&(i, j)| i != number.row || j < number.col || j >= number.col + number.len)
     }
 
-    fn is_symbol(&self, i: i32, j: i32) -> bool {
-        if let Some(b) = self.get(i, j) {
-            !b.is_ascii_digit() && b != b'.'
-        } else {
-            false
-        }
+    /// Whether the number touches any symbol.
+    pub fn is_adjacent_to_symbol(&self, number: &Number) -> bool {
+        self.surroundings(number).any(|p| is_symbol(self.map[p]))
     }
 
-    fn is_adjacent_to_symbol(&self, number: &Number) -> bool {
-        number
-            .surroundings()
+    /// The full adjacency graph between numbers and symbols.
+    pub fn adjacency(&self) -> Adjacency {
+        let symbols: Vec<_> = self
+            .map
             .iter()
-            .any(|&(i, j)| self.is_symbol(i, j))
-    }
-
-    fn is_star(&self, i: i32, j: i32) -> bool {
-        self.get(i, j) == Some(b'*')
+            .filter(|&(_, &b)| is_symbol(b))
+            .map(|(p, &b)| (p, b))
+            .collect();
+        let index: HashMap<Pos, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(k, &(p, _))| (p, k))
+            .collect();
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        let number_symbols: Vec<Vec<usize>> = self
+            .numbers
+            .iter()
+            .enumerate()
+            .map(|(n, number)| {
+                let touching: Vec<_> = self
+                    .surroundings(number)
+                    .filter_map(|p| index.get(&p).copied())
+                    .collect();
+                for &s in touching.iter() {
+                    symbol_numbers[s].push(n);
+                }
+                touching
+            })
+            .collect();
+        Adjacency {
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        }
     }
 
-    fn add_number_to_stars(&self, number: &Number, stars_map: &mut StarsHashMap) {
-        for (i, j) in number.surroundings() {
-            if self.is_star(i, j) {
-                stars_map.entry((i, j)).or_default().push(number.num);
-            }
-        }
+    /// Positions of the `symbol` characters (any symbol if `None`) together with the
+    /// part numbers touching them, keeping only those touching exactly `count` numbers
+    /// if it is given.
+    pub fn query(&self, symbol: Option<u8>, count: Option<usize>) -> Vec<(Pos, Vec<u32>)> {
+        let graph = self.adjacency();
+        graph
+            .symbols
+            .iter()
+            .zip(graph.symbol_numbers.iter())
+            .filter(|&(&(_, b), numbers)| {
+                symbol.is_none_or(|s| s == b) && count.is_none_or(|c| c == numbers.len())
+            })
+            .map(|(&(p, _), numbers)| (p, numbers.iter().map(|&n| self.numbers[n].num).collect()))
+            .collect()
     }
 }
 
-pub fn part1(path: &str) {
-    let engine = Engine::new(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let engine = Engine::new(text)?;
+    bench::parsed();
     let sum: u32 = engine
         .numbers
         .iter()
         .filter(|number| engine.is_adjacent_to_symbol(number))
         .map(|number| number.num)
         .sum();
-    println!("{sum}");
+    Ok(sum.into())
 }
 
-pub fn part2(path: &str) {
-    let engine = Engine::new(path);
-    let mut stars_map: StarsHashMap = HashMap::new();
-    engine
-        .numbers
+pub fn part2(text: &str) -> Result<Answer> {
+    let engine = Engine::new(text)?;
+    bench::parsed();
+    let sum: u32 = engine
+        .query(Some(b'*'), Some(2))
         .iter()
-        .for_each(|number| engine.add_number_to_stars(number, &mut stars_map));
-    let sum: u32 = stars_map
-        .values()
-        .filter_map(|v| {
-            if v.len() == 2 {
-                Some(v[0] * v[1])
-            } else {
-                None
-            }
-        })
+        .map(|(_, numbers)| numbers[0] * numbers[1])
         .sum();
-    println!("{sum}");
+    Ok(sum.into())
 }
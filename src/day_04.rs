@@ -1,64 +1,68 @@
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Card {
-    num_winning: usize,
+/// Scratchcard reduced to its number of winning numbers.
+pub struct Card {
+    pub num_winning: usize,
 }
 
 impl Card {
-    fn parse(s: &str) -> Card {
-        assert!(s.starts_with("Card"));
-        let colon = s.find(':').unwrap();
-        let parts = s[colon + 1..].split(" | ").collect::<Vec<_>>();
-        assert_eq!(parts.len(), 2);
-        let winning = Card::parse_numbers(parts[0]);
-        let numbers = Card::parse_numbers(parts[1]);
-        Card {
+    /// Parses a line such as `Card 1: 41 48 | 83 86 17`.
+    pub fn parse(s: &str) -> Result<Card> {
+        let (_, rest): (u32, _) = input::record(s, "Card")?;
+        let (winning, numbers) = input::split_once(s, rest, "|")?;
+        let winning: HashSet<u32> = input::numbers(s, winning)?.into_iter().collect();
+        let numbers: HashSet<u32> = input::numbers(s, numbers)?.into_iter().collect();
+        Ok(Card {
             num_winning: winning.intersection(&numbers).count(),
-        }
-    }
-
-    fn parse_numbers(s: &str) -> HashSet<u32> {
-        s.split_ascii_whitespace()
-            .map(|x| x.parse().unwrap())
-            .collect()
+        })
     }
 
-    fn points(&self) -> u32 {
+    /// Points the card is worth.
+    pub fn points(&self) -> u32 {
         if self.num_winning > 0 {
-            1 << self.num_winning - 1
+            1 << (self.num_winning - 1)
         } else {
             0
         }
     }
 }
 
-pub fn part1(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let sum: u32 = reader
-        .lines()
-        .map(|s| Card::parse(&s.unwrap()).points())
-        .sum();
-    println!("{sum}");
+/// Total number of cards, originals and copies, once every card has won its copies of
+/// the following cards; fails if it does not fit into a `u64`.
+///
+/// Each card adds its count to a range of later cards, so the counts are kept as a
+/// running total plus the amounts that stop applying at each card, in linear time.
+pub fn total_cards(cards: &[Card]) -> Result<u64> {
+    let overflow = |i: usize| Error::new(format!("Number of copies of card {} overflows", i + 1));
+    let mut ending = vec![0u64; cards.len() + 1];
+    let mut active = 0u64;
+    let mut total = 0u64;
+    for (i, card) in cards.iter().enumerate() {
+        active -= ending[i];
+        let count = active.checked_add(1).ok_or_else(|| overflow(i))?;
+        total = total.checked_add(count).ok_or_else(|| overflow(i))?;
+        let end = (i + card.num_winning + 1).min(cards.len());
+        if end > i + 1 {
+            active = active.checked_add(count).ok_or_else(|| overflow(i))?;
+            ending[end] = ending[end].checked_add(count).ok_or_else(|| overflow(i))?;
+        }
+    }
+    Ok(total)
+}
+
+pub fn part1(text: &str) -> Result<Answer> {
+    let cards = input::parse_lines(text, Card::parse)?;
+    bench::parsed();
+    let sum: u32 = cards.iter().map(|card| card.points()).sum();
+    Ok(sum.into())
 }
 
-pub fn part2(path: &str) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let cards = reader
-        .lines()
-        .map(|s| Card::parse(&s.unwrap()))
-        .collect::<Vec<_>>();
-    // Note: A more efficient implementation could be done using Segment Trees.
-    let mut count = vec![1u32; cards.len()];
-    cards.iter().enumerate().for_each(|(i, card)| {
-        let upper_bound = (i + card.num_winning + 1).min(cards.len());
-        for j in i + 1..upper_bound {
-            count[j] += count[i];
-        }
-    });
-    let sum: u32 = count.iter().sum();
-    println!("{sum}");
+pub fn part2(text: &str) -> Result<Answer> {
+    let cards = input::parse_lines(text, Card::parse)?;
+    bench::parsed();
+    Ok(total_cards(&cards)?.into())
 }
@@ -1,201 +1,260 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::input;
+use crate::interval::{Interval, IntervalSet};
+use crate::Answer;
+use std::cell::OnceCell;
+use std::fmt;
 
-struct Range {
-    dest_start: u64,
-    src_start: u64,
-    src_len: u64,
+/// Single `dest src len` line of an almanac map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Range {
+    pub dest_start: u64,
+    pub src_start: u64,
+    pub src_len: u64,
 }
 
 impl Range {
-    fn parse(s: &str) -> Range {
-        let nums = parse_numbers(s);
-        assert_eq!(nums.len(), 3);
-        Range {
+    /// Parses a line such as `50 98 2`.
+    pub fn parse(s: &str) -> Result<Range> {
+        let nums: Vec<u64> = input::numbers(s, s)?;
+        if nums.len() != 3 {
+            return Err(Error::at(
+                s,
+                s,
+                format!("Expected 3 numbers, found {}", nums.len()),
+            ));
+        }
+        Ok(Range {
             dest_start: nums[0],
             src_start: nums[1],
             src_len: nums[2],
-        }
+        })
+    }
+
+    /// End of the source range (exclusive).
+    pub fn src_end(&self) -> u64 {
+        self.src_start + self.src_len
     }
 }
 
-struct Map {
-    name: String,
-    ranges: Vec<Range>,
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.dest_start, self.src_start, self.src_len)
+    }
 }
 
-#[derive(Clone)]
-struct Interval {
-    start: u64,
-    end: u64, // Exclusive
+/// Almanac map such as `seed-to-soil`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Map {
+    pub name: String,
+    /// Sorted by `src_start`, without overlaps.
+    pub ranges: Vec<Range>,
 }
 
 impl Map {
-    fn upper_bound(&self, x: u64, hint: usize) -> usize {
-        let mut left = hint;
-        let mut right = self.ranges.len();
-        while left < right {
-            let mid = (left + right) / 2;
-            if self.ranges[mid].src_start > x {
-                right = mid;
-            } else {
-                left = mid + 1;
-            }
+    /// Maps a single value in O(log n).
+    pub fn apply(&self, x: u64) -> u64 {
+        let i = self.ranges.partition_point(|range| range.src_start <= x);
+        match i.checked_sub(1).map(|i| &self.ranges[i]) {
+            Some(range) if x < range.src_end() => range.dest_start + x - range.src_start,
+            _ => x,
         }
-        left
     }
 
-    fn apply(&self, x: u64) -> u64 {
-        let i = self.upper_bound(x, 0);
-        if i > 0 {
-            let range = &self.ranges[i - 1];
-            if x >= range.src_start && x < range.src_start + range.src_len {
-                range.dest_start + x - range.src_start
-            } else {
-                x
-            }
-        } else {
-            x
-        }
-    }
-
-    fn apply_interval(&self, int: &Interval) -> Vec<Interval> {
-        let mut out = vec![];
+    /// Maps an interval of values in O(log n + k), where k is the number of ranges
+    /// it overlaps.
+    pub fn apply_interval(&self, int: Interval) -> IntervalSet {
+        let mut out = IntervalSet::new();
         let mut x = int.start;
-        let mut i = 0usize;
+        let mut i = self.ranges.partition_point(|range| range.src_end() <= x);
         while x < int.end {
-            i = self.upper_bound(x, i);
-            if i > 0 {
-                let range = &self.ranges[i - 1];
-                if x >= range.src_start && x < range.src_start + range.src_len {
-                    if int.end <= range.src_start + range.src_len {
-                        out.push(Interval {
-                            start: range.dest_start + x - range.src_start,
-                            end: range.dest_start + int.end - range.src_start,
-                        });
-                        x = int.end;
-                    } else {
-                        out.push(Interval {
-                            start: range.dest_start + x - range.src_start,
-                            end: range.dest_start + range.src_len,
-                        });
-                        x = range.src_start + range.src_len;
-                    }
-                } else if i < self.ranges.len() {
-                    let range = &self.ranges[i];
-                    out.push(Interval {
-                        start: x,
-                        end: range.src_start.min(int.end),
-                    });
-                    x = range.src_start;
-                } else {
-                    out.push(Interval {
-                        start: x,
-                        end: int.end,
-                    });
-                    x = int.end;
+            let end = match self.ranges.get(i) {
+                Some(range) if range.src_start <= x => {
+                    let end = range.src_end().min(int.end);
+                    let shift = |y| range.dest_start + y - range.src_start;
+                    out.insert(Interval::new(shift(x), shift(end)));
+                    i += 1;
+                    end
                 }
-            } else {
-                let range = &self.ranges[i];
-                out.push(Interval {
-                    start: x,
-                    end: range.src_start.min(int.end),
-                });
-                x = range.src_start;
-            }
+                Some(range) => {
+                    let end = range.src_start.min(int.end);
+                    out.insert(Interval::new(x, end));
+                    end
+                }
+                None => {
+                    out.insert(Interval::new(x, int.end));
+                    int.end
+                }
+            };
+            x = end;
         }
-        out.sort_by_key(|int| int.start);
         out
     }
 
-    fn apply_intervals(&self, intervals: Vec<Interval>) -> Vec<Interval> {
-        let mut out = intervals
+    /// Maps a set of values.
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.intervals()
             .iter()
-            .map(|int| self.apply_interval(int))
-            .collect::<Vec<_>>()
-            .concat();
-        out.sort_by_key(|int| int.start);
-        out
+            .map(|&int| self.apply_interval(int))
+            .fold(IntervalSet::new(), |acc, s| acc.union(&s))
+    }
+
+    /// Single map doing the same as applying this map and then `next`, as sorted ranges
+    /// with the parts that map values to themselves left out.
+    pub fn compose(&self, next: &Map) -> Map {
+        // Both maps are shifts between consecutive breakpoints: the boundaries of this
+        // map and the values this map sends to the boundaries of `next`.
+        let mut cuts = vec![];
+        for range in self.ranges.iter() {
+            cuts.extend([range.src_start, range.src_end()]);
+            let dest = range.dest_start..range.dest_start + range.src_len;
+            for b in next.ranges.iter().flat_map(|r| [r.src_start, r.src_end()]) {
+                if dest.contains(&b) {
+                    cuts.push(b - range.dest_start + range.src_start);
+                }
+            }
+        }
+        for b in next.ranges.iter().flat_map(|r| [r.src_start, r.src_end()]) {
+            if self.apply(b) == b {
+                cuts.push(b);
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut ranges: Vec<Range> = vec![];
+        for w in cuts.windows(2) {
+            let (start, end) = (w[0], w[1]);
+            let dest_start = next.apply(self.apply(start));
+            if dest_start == start {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last)
+                    if last.src_end() == start && last.dest_start + last.src_len == dest_start =>
+                {
+                    last.src_len += end - start;
+                }
+                _ => ranges.push(Range {
+                    dest_start,
+                    src_start: start,
+                    src_len: end - start,
+                }),
+            }
+        }
+        let source = self.name.split_once("-to-").map_or(&*self.name, |(a, _)| a);
+        let target = next.name.split_once("-to-").map_or(&*next.name, |(_, b)| b);
+        Map {
+            name: format!("{source}-to-{target}"),
+            ranges,
+        }
     }
 }
 
-struct Input {
-    seeds: Vec<u64>,
+impl fmt::Display for Map {
+    /// Formats the map as in the almanac, with its ranges in increasing source order.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} map:", self.name)?;
+        for range in self.ranges.iter() {
+            writeln!(f, "{range}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Seeds and the chain of maps from the almanac.
+pub struct Input {
+    pub seeds: Vec<u64>,
     maps: Vec<Map>,
+    composed: OnceCell<Map>,
 }
 
 impl Input {
-    fn read(path: &str) -> Input {
-        let file = File::open(path).unwrap();
-        let reader = io::BufReader::new(file);
-        let mut lines = reader.lines();
-        let seeds = lines.next().unwrap().unwrap();
-        assert!(seeds.starts_with("seeds: "));
-        let seeds = parse_numbers(&seeds[7..]);
-        lines.next();
-        let mut maps = vec![];
-        loop {
-            let mut map = Map {
-                name: String::from(""),
-                ranges: vec![],
-            };
-            loop {
-                let line = lines.next();
-                if line.is_none() {
-                    break; // End of input
-                }
-
-                let line = line.unwrap().unwrap();
-                if line.trim().is_empty() {
-                    break; // Empty line marking the start of a new block
-                }
-
-                if map.name.is_empty() {
-                    map.name = line.strip_suffix(" map:").unwrap().to_string();
-                    continue;
+    /// Parses the almanac.
+    pub fn parse(text: &str) -> Result<Input> {
+        let blocks = input::blocks(text);
+        let (seeds, map_blocks) = blocks
+            .split_first()
+            .ok_or_else(|| Error::new("Expected 'seeds:'"))?;
+        if seeds.lines.len() > 1 {
+            return Err(Error::new("Expected a blank line after the seeds")).line(seeds.line + 1);
+        }
+        let line = seeds.lines[0];
+        let seeds = input::numbers(line, input::header(line, "seeds")?).line(seeds.line)?;
+        let mut maps = map_blocks
+            .iter()
+            .map(|block| {
+                let (header, ranges) = block.split_first();
+                let name = header
+                    .strip_suffix(" map:")
+                    .ok_or_else(|| Error::at(header, header, "Expected ' map:'"))
+                    .line(block.line)?;
+                let ranges = ranges.parse_lines(Range::parse)?;
+                if ranges.is_empty() {
+                    return Err(Error::new("Expected at least one range")).line(block.line);
                 }
-
-                map.ranges.push(Range::parse(&line));
-            }
-            if map.ranges.is_empty() {
-                break;
-            }
-
+                Ok(Map {
+                    name: name.to_string(),
+                    ranges,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (map, block) in maps.iter_mut().zip(map_blocks) {
             map.ranges.sort_by_key(|range| range.src_start);
-            maps.push(map);
+            if map
+                .ranges
+                .windows(2)
+                .any(|w| w[0].src_end() > w[1].src_start)
+            {
+                return Err(Error::new("Overlapping source ranges")).line(block.line);
+            }
         }
-        Input { seeds, maps }
+        Ok(Input {
+            seeds,
+            maps,
+            composed: OnceCell::new(),
+        })
+    }
+
+    /// The chain of maps, from seeds to locations.
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
     }
-}
 
-fn parse_numbers(s: &str) -> Vec<u64> {
-    s.split_ascii_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect()
+    /// The whole chain of maps composed into one, computed on first use.
+    pub fn composed(&self) -> &Map {
+        self.composed.get_or_init(|| match self.maps.split_first() {
+            Some((first, rest)) => rest.iter().fold(first.clone(), |acc, map| acc.compose(map)),
+            None => Map {
+                name: String::from("seed-to-seed"),
+                ranges: vec![],
+            },
+        })
+    }
 }
 
-pub fn part1(path: &str) {
-    let input = Input::read(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let input = Input::parse(text)?;
+    bench::parsed();
+    let map = input.composed();
     let min_location = input
         .seeds
         .iter()
-        .map(|&x| input.maps.iter().fold(x, |acc, map| map.apply(acc)))
+        .map(|&x| map.apply(x))
         .min()
-        .unwrap();
-    println!("{min_location}");
+        .ok_or_else(|| Error::new("No seeds"))?;
+    Ok(min_location.into())
 }
 
-pub fn part2(path: &str) {
-    let input = Input::read(path);
-    let initial_intervals = (0usize..input.seeds.len() / 2)
-        .map(|i| Interval {
-            start: input.seeds[2 * i],
-            end: input.seeds[2 * i] + input.seeds[2 * i + 1],
-        })
-        .collect::<Vec<_>>();
-    let final_intervals = input
-        .maps
-        .iter()
-        .fold(initial_intervals, |acc, map| map.apply_intervals(acc));
-    println!("{}", final_intervals[0].start);
+pub fn part2(text: &str) -> Result<Answer> {
+    let input = Input::parse(text)?;
+    bench::parsed();
+    let seeds: IntervalSet = input
+        .seeds
+        .chunks_exact(2)
+        .map(|s| Interval::new(s[0], s[0] + s[1]))
+        .collect();
+    let locations = input.composed().apply_set(&seeds);
+    let min_location = locations.min().ok_or_else(|| Error::new("No seeds"))?;
+    Ok(min_location.into())
 }
@@ -1,60 +1,96 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-
-fn read_input(path: &str) -> (String, String) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let mut lines = reader.lines();
-    let (line1, line2) = (
-        lines.next().unwrap().unwrap(),
-        lines.next().unwrap().unwrap(),
-    );
-    assert!(line1.starts_with("Time:"));
-    assert!(line2.starts_with("Distance:"));
-    (line1, line2)
-}
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::input;
+use crate::Answer;
 
-fn parse_numbers(s: &str) -> Vec<f64> {
-    s.split_ascii_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect()
+/// The `Time:` and `Distance:` lines together with their values.
+fn read_input(text: &str) -> Result<[(&str, &str); 2]> {
+    let mut lines = text.lines();
+    let (line1, line2) = (lines.next().unwrap_or(""), lines.next().unwrap_or(""));
+    let times = input::header(line1, "Time").line(1)?;
+    let distances = input::header(line2, "Distance").line(2)?;
+    Ok([(line1, times), (line2, distances)])
 }
 
-fn parse_concat_number(s: &str) -> f64 {
-    s.split_ascii_whitespace()
-        .collect::<Vec<_>>()
-        .concat()
+fn parse_concat_number(line: &str, s: &str) -> Result<u64> {
+    let concat = s.split_ascii_whitespace().collect::<Vec<_>>().concat();
+    concat
         .parse()
-        .unwrap()
+        .map_err(|_| Error::at(line, s.trim_start(), format!("Invalid number: '{concat}'")))
 }
 
+/// Races up to this time are also solved by brute force in [`win_count_checked`].
+pub const CROSS_CHECK_LIMIT: u64 = 10_000;
+
 // (t - x) * x > d
 // x^2 - t * x + d < 0
-// (x - t/2)^2 - t^2 / 4 + d < 0
-// |x - t/2| < sqrt(t^2 / 4 - d)
-fn win_count(t: f64, d: f64) -> u64 {
-    let k = f64::sqrt(t * t / 4.0 - d);
-    let l = ((t / 2.0 - k).floor() + 1.0).max(0.0) as u64;
-    let r = ((t / 2.0 + k).ceil() - 1.0) as u64;
-    r - l + 1
+// (2x - t)^2 < t^2 - 4d
+/// Number of ways to beat the record distance `d` in a race of time `t`.
+pub fn win_count(t: u64, d: u64) -> u64 {
+    let (t, d) = (t as u128, d as u128);
+    let disc = t * t;
+    if disc <= 4 * d {
+        return 0;
+    }
+    // Largest y = |2x - t| with y^2 < disc and the same parity as t; each such y > 0
+    // gives two hold times and y = 0 gives one, so there are y + 1 of them.
+    let mut y = (disc - 4 * d - 1).isqrt();
+    if y % 2 != t % 2 {
+        if y == 0 {
+            return 0;
+        }
+        y -= 1;
+    }
+    (y + 1) as u64
+}
+
+/// Number of ways to beat the record, trying every hold time.
+pub fn win_count_brute(t: u64, d: u64) -> u64 {
+    (0..=t)
+        .filter(|&x| x as u128 * (t - x) as u128 > d as u128)
+        .count() as u64
+}
+
+/// [`win_count`], compared with [`win_count_brute`] for races no longer than
+/// [`CROSS_CHECK_LIMIT`]; fails if the two disagree.
+pub fn win_count_checked(t: u64, d: u64) -> Result<u64> {
+    let count = win_count(t, d);
+    if t <= CROSS_CHECK_LIMIT {
+        let brute = win_count_brute(t, d);
+        if count != brute {
+            return Err(Error::new(format!(
+                "Race of time {t} and record {d}: found {count} ways, brute force found {brute}"
+            )));
+        }
+    }
+    Ok(count)
 }
 
-pub fn part1(path: &str) {
-    let (line1, line2) = read_input(path);
-    let times = parse_numbers(&line1[5..]);
-    let distances = parse_numbers(&line2[9..]);
+pub fn part1(text: &str) -> Result<Answer> {
+    let [(line1, times), (line2, distances)] = read_input(text)?;
+    let times: Vec<u64> = input::numbers(line1, times).line(1)?;
+    let distances: Vec<u64> = input::numbers(line2, distances).line(2)?;
+    if times.len() != distances.len() {
+        return Err(Error::new(format!(
+            "Found {} times but {} distances",
+            times.len(),
+            distances.len()
+        )));
+    }
+    bench::parsed();
     let num_ways: u64 = times
         .iter()
         .zip(distances.iter())
         .map(|(&t, &d)| win_count(t, d))
         .product();
-    println!("{num_ways}");
+    Ok(num_ways.into())
 }
 
-pub fn part2(path: &str) {
-    let (line1, line2) = read_input(path);
-    let time = parse_concat_number(&line1[5..]);
-    let distance = parse_concat_number(&line2[9..]);
+pub fn part2(text: &str) -> Result<Answer> {
+    let [(line1, time), (line2, distance)] = read_input(text)?;
+    let time = parse_concat_number(line1, time).line(1)?;
+    let distance = parse_concat_number(line2, distance).line(2)?;
+    bench::parsed();
     let num_ways = win_count(time, distance);
-    println!("{num_ways}");
+    Ok(num_ways.into())
 }
@@ -1,16 +1,20 @@
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct HandBuilder {
+/// Builds comparable hands, optionally treating `J` as a Joker.
+pub struct HandBuilder {
     map: HashMap<u8, usize>,
     joker: bool,
 }
 
-type Hand = ([usize; 5], [usize; 5]);
+/// Hand type histogram followed by the card ranks; compares by strength.
+pub type Hand = ([usize; 5], [usize; 5]);
 
 impl HandBuilder {
-    fn new(joker: bool) -> HandBuilder {
+    pub fn new(joker: bool) -> HandBuilder {
         let priority = if joker {
             "J23456789TQKA"
         } else {
@@ -22,11 +26,18 @@ impl HandBuilder {
         }
     }
 
-    fn build(&self, s: &str) -> Hand {
+    /// Builds the hand from a string such as `32T3K`.
+    pub fn build(&self, s: &str) -> Result<Hand> {
+        if s.len() != 5 {
+            return Err(Error::at(s, s, format!("Expected 5 cards, found '{s}'")));
+        }
         let mut cards = [0; 5];
-        s.bytes()
-            .enumerate()
-            .for_each(|(i, b)| cards[i] = self.map[&b]);
+        for (i, b) in s.bytes().enumerate() {
+            cards[i] = *self
+                .map
+                .get(&b)
+                .ok_or_else(|| Error::at(s, &s[i..], format!("Invalid card: '{}'", b as char)))?;
+        }
         let mut card_counts = [0usize; 13];
         cards.iter().for_each(|&c| card_counts[c] += 1);
         if self.joker {
@@ -46,32 +57,25 @@ impl HandBuilder {
             .iter()
             .filter(|&&c| c > 0)
             .for_each(|&c| hist[5 - c] += 1);
-        (hist, cards)
+        Ok((hist, cards))
     }
 }
 
-fn solve(path: &str, joker: bool) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+fn solve(text: &str, joker: bool) -> Result<usize> {
     let hand_builder = HandBuilder::new(joker);
-    let mut input: Vec<(Hand, usize)> = reader
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let parts: Vec<_> = line.split(' ').collect();
-            let input = (hand_builder.build(parts[0]), parts[1].parse().unwrap());
-            input
-        })
-        .collect();
+    let mut input: Vec<(Hand, usize)> = input::parse_lines(text, |line| {
+        let (hand, bid) = input::split_once(line, line, " ")?;
+        Ok((hand_builder.build(hand)?, input::parse(line, bid)?))
+    })?;
+    bench::parsed();
     input.sort();
-    let sum: usize = input.iter().enumerate().map(|(i, h)| (i + 1) * h.1).sum();
-    println!("{sum}");
+    Ok(input.iter().enumerate().map(|(i, h)| (i + 1) * h.1).sum())
 }
 
-pub fn part1(path: &str) {
-    solve(path, false);
+pub fn part1(text: &str) -> Result<Answer> {
+    Ok(solve(text, false)?.into())
 }
 
-pub fn part2(path: &str) {
-    solve(path, true);
+pub fn part2(text: &str) -> Result<Answer> {
+    Ok(solve(text, true)?.into())
 }
@@ -1,29 +1,70 @@
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::input;
+use crate::math;
+use crate::Answer;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Input {
-    instructions: String,
-    map: HashMap<String, (String, String)>,
+/// Left/right instructions and the network of nodes.
+pub struct Input {
+    pub instructions: String,
+    pub map: HashMap<String, (String, String)>,
 }
 
 impl Input {
-    fn read(path: &str) -> Input {
-        let file = File::open(path).unwrap();
-        let reader = io::BufReader::new(file);
-        let mut lines = reader.lines();
-        let instructions = lines.next().unwrap().unwrap();
-        let map = lines
-            .skip(1) // Skip the newline
-            .filter_map(Result::ok)
-            .map(|line| {
-                (
-                    line[0..3].to_string(),
-                    (line[7..10].to_string(), line[12..15].to_string()),
-                )
-            })
+    /// Parses the instructions and the network.
+    pub fn parse(text: &str) -> Result<Input> {
+        let blocks = input::blocks(text);
+        let (first, nodes) = match blocks.as_slice() {
+            [first, nodes] if first.lines.len() == 1 => (first, nodes),
+            [] => return Err(Error::new("Missing instructions")).line(1),
+            _ => {
+                return Err(Error::new(
+                    "Expected the instructions and the nodes separated by a blank line",
+                ))
+            }
+        };
+        let instructions = first.lines[0];
+        if let Some(i) = instructions.find(|c| c != 'L' && c != 'R') {
+            return Err(Error::at(
+                instructions,
+                &instructions[i..],
+                "Expected 'L' or 'R'",
+            ))
+            .line(first.line);
+        }
+        let parsed = nodes.parse_lines(Input::parse_node)?;
+        let map: HashMap<_, _> = parsed
+            .iter()
+            .map(|&(node, l, r)| (node.to_string(), (l.to_string(), r.to_string())))
             .collect();
-        Input { instructions, map }
+        for (k, (line, &(_, l, r))) in nodes.lines.iter().zip(parsed.iter()).enumerate() {
+            for child in [l, r] {
+                if !map.contains_key(child) {
+                    return Err(Error::at(line, child, format!("Unknown node: '{child}'")))
+                        .line(nodes.line + k);
+                }
+            }
+        }
+        Ok(Input {
+            instructions: instructions.to_string(),
+            map,
+        })
+    }
+
+    fn parse_node(line: &str) -> Result<(&str, &str, &str)> {
+        let (node, children) = input::split_once(line, line, " = ")?;
+        let children = children
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| Error::at(line, children, "Expected '(<left>, <right>)'"))?;
+        let (l, r) = input::split_once(line, children, ", ")?;
+        for name in [node, l, r] {
+            if name.len() != 3 {
+                return Err(Error::at(line, name, format!("Invalid node: '{name}'")));
+            }
+        }
+        Ok((node, l, r))
     }
 }
 
@@ -32,103 +73,81 @@ struct GhostCycle {
     loop_len: i64,
 }
 
-fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let t = b;
-        b = a % b;
-        a = t;
-    }
-    a
-}
-
-fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
-    if a == 0 {
-        // b == 0 * a + 1 * b
-        (b, 0, 1)
-    } else {
-        // g == x * (b % a) + y * a
-        // g == x * (b - (b / a) * a) + y * a
-        let (g, x, y) = extended_gcd(b % a, a);
-        (g, y - x * (b / a), x)
-    }
-}
-
-fn mod_inverse(a: i64, m: i64) -> i64 {
-    let (g, x, _) = extended_gcd(a, m);
-    assert_eq!(g, 1);
-    (x % m + m) % m
-}
-
-fn chinese_remainder(residues: &[i64], modulii: &[i64]) -> i64 {
-    let n: i64 = modulii.iter().product();
-    let mut sum = 0;
-    for (&r, &m) in residues.iter().zip(modulii) {
-        let p = n / m;
-        let m_inv = mod_inverse(p, m);
-        sum += r * m_inv * p;
+pub fn part1(text: &str) -> Result<Answer> {
+    let input = Input::parse(text)?;
+    bench::parsed();
+    for name in ["AAA", "ZZZ"] {
+        if !input.map.contains_key(name) {
+            return Err(Error::new(format!("Missing node '{name}'")));
+        }
     }
-    (sum % n + n) % n
-}
-
-pub fn part1(path: &str) {
-    let input = Input::read(path);
     let n = input.instructions.len();
     let mut node = &String::from("AAA");
     let mut steps = 0;
     while node != "ZZZ" {
-        let &(ref l, ref r) = input.map.get(node).unwrap();
+        // Past this many steps some (node, instruction) state has repeated.
+        if steps == n * input.map.len() {
+            return Err(Error::new("Node 'ZZZ' cannot be reached from 'AAA'"));
+        }
+        let (l, r) = input.map.get(node).unwrap();
         let b = input.instructions.as_bytes()[steps % n];
         node = if b == b'L' { l } else { r };
         steps += 1;
     }
-    println!("{steps}");
+    Ok(steps.into())
 }
 
-pub fn part2(path: &str) {
-    let input = Input::read(path);
+pub fn part2(text: &str) -> Result<Answer> {
+    let input = Input::parse(text)?;
+    bench::parsed();
     let n = input.instructions.len() as i64;
     let mut ghost_cycles = vec![];
-    for mut node in input.map.keys().filter(|&node| node.as_bytes()[2] == b'A') {
-        let mut end_pos = 0i64;
+    for start in input.map.keys().filter(|&node| node.as_bytes()[2] == b'A') {
+        let mut node = start;
+        let mut end_pos = None;
         let loop_len;
         let mut steps = 0i64;
         let mut visited: HashMap<(&String, i64), i64> = HashMap::new();
         loop {
             let state = (node, steps % n);
-            if node.as_bytes()[2] == b'Z' {
-                // There is exactly one end state inside the cycle.
-                assert_eq!(end_pos, 0);
-                end_pos = steps;
-            }
             if visited.contains_key(&state) {
                 loop_len = steps - visited[&state];
                 break;
             }
+            if node.as_bytes()[2] == b'Z' {
+                // There must be exactly one end state before the path repeats.
+                if end_pos.is_some() {
+                    return Err(Error::new(format!(
+                        "Ghost starting at '{start}' passes several end nodes"
+                    )));
+                }
+                end_pos = Some(steps);
+            }
 
             visited.insert(state, steps);
-            let &(ref l, ref r) = input.map.get(node).unwrap();
+            let (l, r) = input.map.get(node).unwrap();
             let b = input.instructions.as_bytes()[(steps % n) as usize];
             node = if b == b'L' { l } else { r };
             steps += 1;
         }
+        let end_pos = end_pos.ok_or_else(|| {
+            Error::new(format!(
+                "Ghost starting at '{start}' never reaches an end node"
+            ))
+        })?;
         ghost_cycles.push(GhostCycle { end_pos, loop_len })
     }
-    // The ghost cycle lenghts share the same GCD.
-    let ghost_gcd = gcd(ghost_cycles[0].loop_len, ghost_cycles[1].loop_len);
-    // The end state positions yield the same reminder when divided by the shared GCD.
-    let ghost_rem = ghost_cycles[0].end_pos % ghost_gcd;
-    // Move by ghost_offset steps into the cycle so that the resulting position is divisible by the shared GCD.
-    let ghost_offset = ghost_gcd - ghost_rem;
-    // Solve using Chinese Reminder Theorem.
-    // Dividing by the shared GCD will lead to pairwise coprime cycle lenghts.
-    let residues: Vec<_> = ghost_cycles
-        .iter()
-        .map(|c| (c.end_pos - ghost_offset) / ghost_gcd)
-        .collect();
-    let modulii: Vec<_> = ghost_cycles
+    if ghost_cycles.is_empty() {
+        return Err(Error::new("Expected a node ending with 'A'"));
+    }
+    // Each ghost is at its end state after `end_pos + k * loop_len` steps, for any k >= 0.
+    let congruences: Vec<_> = ghost_cycles
         .iter()
-        .map(|c| c.loop_len / ghost_gcd)
+        .map(|c| (c.end_pos as i128, c.loop_len as i128))
         .collect();
-    let steps = ghost_offset + ghost_gcd * chinese_remainder(&residues, &modulii);
-    println!("{steps}");
+    let (rem, lcm) = math::crt(&congruences)
+        .ok_or_else(|| Error::new("The ghosts never reach their end nodes at the same time"))?;
+    let min = ghost_cycles.iter().map(|c| c.end_pos).max().unwrap() as i128;
+    let steps = rem + ((min - rem).max(0) + lcm - 1) / lcm * lcm;
+    Ok(steps.into())
 }
@@ -1,11 +1,10 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::Result;
+use crate::input;
+use crate::Answer;
 
-fn parse_numbers(s: &str) -> Vec<i64> {
-    s.split(' ').map(|x| x.parse().unwrap()).collect()
-}
-
-fn predict(mut x: Vec<i64>) -> i64 {
+/// Extrapolates the next value of the sequence.
+pub fn predict(mut x: Vec<i64>) -> i64 {
     let n = x.len();
     let mut sum = 0;
     for k in 1..n {
@@ -22,7 +21,8 @@ fn predict(mut x: Vec<i64>) -> i64 {
     sum
 }
 
-fn predict_rev(mut x: Vec<i64>) -> i64 {
+/// Extrapolates the previous value of the sequence.
+pub fn predict_rev(mut x: Vec<i64>) -> i64 {
     let n = x.len();
     let mut sum = x[0];
     for k in 1..n {
@@ -43,18 +43,16 @@ fn predict_rev(mut x: Vec<i64>) -> i64 {
     sum
 }
 
-fn solve<F: Fn(Vec<i64>) -> i64>(path: &str, f: F) {
-    let sum: i64 = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .map(|line| f(parse_numbers(&line.unwrap())))
-        .sum();
-    println!("{sum}");
+fn solve<F: Fn(Vec<i64>) -> i64>(text: &str, f: F) -> Result<i64> {
+    let histories = input::parse_lines(text, |line| input::numbers(line, line))?;
+    bench::parsed();
+    Ok(histories.into_iter().map(f).sum())
 }
 
-pub fn part1(path: &str) {
-    solve(path, predict);
+pub fn part1(text: &str) -> Result<Answer> {
+    Ok(solve(text, predict)?.into())
 }
 
-pub fn part2(path: &str) {
-    solve(path, predict_rev);
+pub fn part2(text: &str) -> Result<Answer> {
+    Ok(solve(text, predict_rev)?.into())
 }
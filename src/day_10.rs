@@ -1,88 +1,89 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::ops::Add;
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::grid::{Grid, Pos};
+use crate::render::{self, Scene};
+use crate::Answer;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-struct Coord(i32, i32);
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, other: Coord) -> Coord {
-        Coord(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-fn offsets(b: u8) -> [Coord; 2] {
+fn directions(b: u8) -> Option<[Direction; 2]> {
+    use Direction::*;
     match b {
-        b'|' => [Coord(1, 0), Coord(-1, 0)],
-        b'-' => [Coord(0, 1), Coord(0, -1)],
-        b'L' => [Coord(0, 1), Coord(-1, 0)],
-        b'J' => [Coord(0, -1), Coord(-1, 0)],
-        b'7' => [Coord(0, -1), Coord(1, 0)],
-        b'F' => [Coord(0, 1), Coord(1, 0)],
-        _ => panic!("Invalid input: {}", b as char),
-    }
-}
-
-fn find_start(map: &Vec<Vec<u8>>) -> Coord {
-    for i in 0..map.len() {
-        for j in 0..map[0].len() {
-            if map[i][j] == b'S' {
-                return Coord(i as i32, j as i32);
-            }
-        }
+        b'|' => Some([Down, Up]),
+        b'-' => Some([Right, Left]),
+        b'L' => Some([Right, Up]),
+        b'J' => Some([Left, Up]),
+        b'7' => Some([Left, Down]),
+        b'F' => Some([Right, Down]),
+        _ => None,
     }
-    panic!("Could not find start!");
 }
 
-struct PipeMaze {
-    map: Vec<Vec<u8>>,
-    m: usize,
-    n: usize,
-    s: Coord,
+/// Pipe maze with the start tile replaced by the matching pipe.
+pub struct PipeMaze {
+    pub map: Grid<u8>,
+    pub s: Point,
 }
 
 impl PipeMaze {
-    fn read(path: &str) -> PipeMaze {
-        let mut map: Vec<Vec<u8>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| line.bytes().collect())
-            .collect();
-        let m = map.len();
-        let n = map[0].len();
-        let s = find_start(&map);
-        let (si, sj) = (s.0 as usize, s.1 as usize);
-        let north = si > 0 && [b'|', b'7', b'F'].contains(&map[si - 1][sj]);
-        let south = si < m - 1 && [b'|', b'L', b'J'].contains(&map[si + 1][sj]);
-        let west = sj > 0 && [b'-', b'L', b'F'].contains(&map[si][sj - 1]);
-        let east = sj < n - 1 && [b'-', b'J', b'7'].contains(&map[si][sj + 1]);
-        map[si][sj] = match (north, south, west, east) {
+    /// Parses the maze.
+    pub fn parse(text: &str) -> Result<PipeMaze> {
+        let mut map = Grid::parse(text, b"|-LJ7F.S")?;
+        let (si, sj) = map
+            .find(&b'S')
+            .ok_or_else(|| Error::new("Could not find start"))?;
+        let s = Point::from((si, sj));
+        let connects = |d: Direction, pipes: [u8; 3]| {
+            map.get_point(s.step(d)).is_some_and(|b| pipes.contains(b))
+        };
+        let north = connects(Direction::Up, [b'|', b'7', b'F']);
+        let south = connects(Direction::Down, [b'|', b'L', b'J']);
+        let west = connects(Direction::Left, [b'-', b'L', b'F']);
+        let east = connects(Direction::Right, [b'-', b'J', b'7']);
+        map[(si, sj)] = match (north, south, west, east) {
             (true, true, false, false) => b'|',
             (false, false, true, true) => b'-',
             (true, false, false, true) => b'L',
             (true, false, true, false) => b'J',
             (false, true, true, false) => b'7',
             (false, true, false, true) => b'F',
-            _ => panic!("Invalid start!"),
+            _ => {
+                return Err(Error::at_cell(
+                    si,
+                    sj,
+                    "Start must connect to exactly two pipes",
+                ))
+            }
         };
-        PipeMaze { map, m, n, s }
+        let maze = PipeMaze { map, s };
+        maze.walk_cycle()?;
+        Ok(maze)
     }
 
-    fn neighbors(&self, c: Coord) -> [Coord; 2] {
-        let [d0, d1] = offsets(self.map[c.0 as usize][c.1 as usize]);
-        [c + d0, c + d1]
+    /// Both tiles connected to the pipe at `c`, or `None` if there is no pipe at `c`.
+    pub fn neighbors(&self, c: Point) -> Option<[Point; 2]> {
+        let [d0, d1] = directions(*self.map.get_point(c)?)?;
+        Some([c.step(d0), c.step(d1)])
     }
 
-    fn cycle(&self) -> Vec<Coord> {
+    /// Tiles of the main loop, starting at the start tile.
+    pub fn cycle(&self) -> Vec<Point> {
+        // The loop is validated when reading the maze.
+        self.walk_cycle().unwrap()
+    }
+
+    fn walk_cycle(&self) -> Result<Vec<Point>> {
         let mut out = vec![];
         out.push(self.s);
         let mut c0 = self.s;
-        let mut c1 = self.neighbors(c0)[0];
+        let mut c1 = self.neighbors(c0).unwrap()[0];
         while c1 != self.s {
             out.push(c1);
-            let [n0, n1] = self.neighbors(c1);
+            let [n0, n1] = self
+                .neighbors(c1)
+                .filter(|n| n.contains(&c0))
+                .ok_or_else(|| {
+                    Error::at_cell(c0.i as usize, c0.j as usize, "Pipe loop is broken here")
+                })?;
             if n0 == c0 {
                 c0 = c1;
                 c1 = n1;
@@ -91,55 +92,69 @@ impl PipeMaze {
                 c1 = n0;
             }
         }
-        out
+        Ok(out)
     }
 
-    fn num_inner_nodes(&self) -> usize {
-        let mut sum = 0;
-        let mut is_cycle = vec![vec![false; self.n]; self.m];
+    /// Tiles enclosed by the main loop.
+    pub fn inner_nodes(&self) -> Vec<Pos> {
+        let mut out = vec![];
+        let mut is_cycle = self.map.map(|_| false);
         for c in self.cycle() {
-            is_cycle[c.0 as usize][c.1 as usize] = true;
+            is_cycle[c.pos().unwrap()] = true;
         }
-        for i in 0..self.m {
+        for (i, (row, is_cycle)) in self.map.rows().zip(is_cycle.rows()).enumerate() {
+            let n = row.len();
             let mut is_inside = false;
             let mut j = 0;
-            while j < self.n {
-                if !is_cycle[i][j] {
+            while j < n {
+                if !is_cycle[j] {
                     if is_inside {
-                        sum += 1;
+                        out.push((i, j));
                     }
-                } else if self.map[i][j] == b'|' {
+                } else if row[j] == b'|' {
                     is_inside = !is_inside;
-                } else if self.map[i][j] == b'L' {
+                } else if row[j] == b'L' {
                     j += 1;
-                    while j < self.n && self.map[i][j] == b'-' {
+                    while j < n && row[j] == b'-' {
                         j += 1;
                     }
-                    if self.map[i][j] == b'7' {
+                    if row[j] == b'7' {
                         is_inside = !is_inside;
                     }
-                } else if self.map[i][j] == b'F' {
+                } else if row[j] == b'F' {
                     j += 1;
-                    while j < self.n && self.map[i][j] == b'-' {
+                    while j < n && row[j] == b'-' {
                         j += 1;
                     }
-                    if self.map[i][j] == b'J' {
+                    if row[j] == b'J' {
                         is_inside = !is_inside;
                     }
                 }
                 j += 1;
             }
         }
-        sum
+        out
     }
 }
 
-pub fn part1(path: &str) {
-    let maze = PipeMaze::read(path);
-    println!("{}", (maze.cycle().len() + 1) / 2);
+pub fn part1(text: &str) -> Result<Answer> {
+    let maze = PipeMaze::parse(text)?;
+    bench::parsed();
+    Ok(maze.cycle().len().div_ceil(2).into())
+}
+
+pub fn part2(text: &str) -> Result<Answer> {
+    let maze = PipeMaze::parse(text)?;
+    bench::parsed();
+    Ok(maze.inner_nodes().len().into())
 }
 
-pub fn part2(path: &str) {
-    let maze = PipeMaze::read(path);
-    println!("{}", maze.num_inner_nodes());
+/// Maze with the main loop and the tiles it encloses highlighted.
+pub fn render(text: &str) -> Result<Scene> {
+    let maze = PipeMaze::parse(text)?;
+    let cycle = maze.cycle().into_iter().filter_map(Point::pos);
+    let inner = maze.inner_nodes();
+    Ok(Scene::new(maze.map)
+        .layer(render::YELLOW, cycle)
+        .layer(render::GREEN, inner))
 }
@@ -1,20 +1,20 @@
+use crate::bench;
+use crate::error::Result;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::Answer;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-fn solve(path: &str, empty_space: i64) {
-    let map: Vec<Vec<u8>> = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok)
-        .map(|line| line.bytes().collect())
-        .collect();
-    let (m, n) = (map.len(), map[0].len());
+/// Sum of the shortest paths between all pairs of galaxies, where each empty
+/// row or column is replaced by `empty_space` rows or columns.
+pub fn solve(text: &str, empty_space: i64) -> Result<i64> {
+    let map = Grid::parse(text, b".#")?;
+    bench::parsed();
+    let (m, n) = (map.height(), map.width());
     let empty_rows: HashSet<_> = (0..m)
-        .filter(|&i| map[i].iter().all(|&x| x == b'.'))
-        .collect();
-    let empty_cols: HashSet<_> = (0..n)
-        .filter(|&j| (0..m).all(|i| map[i][j] == b'.'))
+        .filter(|&i| map.row(i).iter().all(|&x| x == b'.'))
         .collect();
+    let empty_cols: HashSet<_> = (0..n).filter(|&j| map.col(j).all(|&x| x == b'.')).collect();
     let mut gal = vec![];
     let mut row = 0i64;
     for i in 0..m {
@@ -30,26 +30,26 @@ fn solve(path: &str, empty_space: i64) {
                 continue;
             }
 
-            if map[i][j] == b'#' {
-                gal.push((row, col));
+            if map[(i, j)] == b'#' {
+                gal.push(Point::new(row, col));
             }
             col += 1;
         }
         row += 1;
     }
     let mut sum = 0;
-    for i in 0..gal.len() - 1 {
+    for i in 0..gal.len() {
         for j in i + 1..gal.len() {
-            sum += (gal[i].0 - gal[j].0).abs() + (gal[i].1 - gal[j].1).abs()
+            sum += gal[i].manhattan(gal[j]);
         }
     }
-    println!("{sum}");
+    Ok(sum)
 }
 
-pub fn part1(path: &str) {
-    solve(path, 2);
+pub fn part1(text: &str) -> Result<Answer> {
+    Ok(solve(text, 2)?.into())
 }
 
-pub fn part2(path: &str) {
-    solve(path, 1_000_000);
+pub fn part2(text: &str) -> Result<Answer> {
+    Ok(solve(text, 1_000_000)?.into())
 }
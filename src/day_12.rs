@@ -1,32 +1,45 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
 
-struct Row {
+/// Row of spring conditions and the sizes of damaged groups.
+pub struct Row {
     con: Vec<u8>,
     grp: Vec<usize>,
 }
 
 impl Row {
-    fn parse(s: &str, unfold: usize) -> Row {
-        let parts: Vec<_> = s.split(' ').collect();
-        assert_eq!(parts.len(), 2);
+    /// Parses a line such as `???.### 1,1,3`, unfolding it `unfold` times.
+    pub fn parse(s: &str, unfold: usize) -> Result<Row> {
+        let (con_part, grp_part) = input::split_once(s, s, " ")?;
+        if let Some(i) = con_part.find(|c| !matches!(c, '.' | '#' | '?')) {
+            let c = con_part[i..].chars().next().unwrap();
+            return Err(Error::at(
+                s,
+                &con_part[i..],
+                format!("Invalid spring: '{c}'"),
+            ));
+        }
+        let grp_once = grp_part
+            .split(',')
+            .map(|g| input::parse(s, g))
+            .collect::<Result<Vec<usize>>>()?;
         let mut con_str = String::from("");
-        let mut grp_str = String::from("");
         for i in 0..unfold {
-            con_str.push_str(parts[0]);
-            grp_str.push_str(parts[1]);
+            con_str.push_str(con_part);
             if i < unfold - 1 {
                 con_str.push('?');
-                grp_str.push(',');
             }
         }
         con_str.push('.');
         let con: Vec<_> = con_str.bytes().collect();
-        let grp = grp_str.split(',').map(|g| g.parse().unwrap()).collect();
-        Row { con, grp }
+        let grp = grp_once.repeat(unfold);
+        Ok(Row { con, grp })
     }
 
-    fn solve(&self) -> i64 {
+    /// Number of arrangements consistent with the row.
+    pub fn solve(&self) -> i64 {
         let mut cache = vec![vec![-1i64; self.grp.len()]; self.con.len()];
         self.solve_part(&mut cache, 0, 0)
     }
@@ -46,7 +59,7 @@ impl Row {
         let suff_len: usize = self.grp[j..].iter().map(|&g| g + 1).sum();
         let g = self.grp[j];
         let mut sum = 0;
-        for k in i..=self.con.len() - suff_len {
+        for k in i..(self.con.len() + 1).saturating_sub(suff_len) {
             if self.con[k..k + g].iter().all(|x| [b'#', b'?'].contains(x))
                 && self.con[k + g] != b'#'
             {
@@ -61,19 +74,16 @@ impl Row {
     }
 }
 
-fn solve(path: &str, unfold: usize) {
-    let sum: i64 = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok)
-        .map(|line| Row::parse(&line, unfold).solve())
-        .sum();
-    println!("{sum}");
+fn solve(text: &str, unfold: usize) -> Result<i64> {
+    let rows = input::parse_lines(text, |line| Row::parse(line, unfold))?;
+    bench::parsed();
+    Ok(rows.iter().map(|row| row.solve()).sum())
 }
 
-pub fn part1(path: &str) {
-    solve(path, 1);
+pub fn part1(text: &str) -> Result<Answer> {
+    Ok(solve(text, 1)?.into())
 }
 
-pub fn part2(path: &str) {
-    solve(path, 5);
+pub fn part2(text: &str) -> Result<Answer> {
+    Ok(solve(text, 5)?.into())
 }
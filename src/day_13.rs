@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
 
 fn reflect(x: &[u64], skip: usize) -> usize {
     let n = x.len();
@@ -15,25 +17,44 @@ fn reflect(x: &[u64], skip: usize) -> usize {
     0
 }
 
-struct Pattern {
+/// Pattern of ash and rocks encoded as bitmasks of rows and columns.
+#[derive(Default)]
+pub struct Pattern {
     rows: Vec<u64>,
     cols: Vec<u64>,
 }
 
 impl Pattern {
-    fn new() -> Pattern {
+    pub fn new() -> Pattern {
         Pattern {
             rows: vec![],
             cols: vec![],
         }
     }
 
-    fn append(&mut self, line: &[u8]) {
+    /// Appends a row of the pattern; patterns are limited to 64 rows and columns.
+    pub fn append(&mut self, s: &str) -> Result<()> {
+        if let Some(i) = s.find(|c| c != '.' && c != '#') {
+            let c = s[i..].chars().next().unwrap();
+            return Err(Error::at(s, &s[i..], format!("Invalid tile: '{c}'")));
+        }
+        let line = s.as_bytes();
         let n = line.len();
         if self.cols.is_empty() {
             self.cols = vec![0; n];
         }
+        if n != self.cols.len() {
+            return Err(Error::at(
+                s,
+                s,
+                format!("Expected {} tiles, found {n}", self.cols.len()),
+            ));
+        }
+        if n > 64 || self.rows.len() == 64 {
+            return Err(Error::at(s, s, "Pattern is larger than 64x64"));
+        }
         let mut row = 0;
+        #[allow(clippy::needless_range_loop)]
         for i in 0..n {
             row <<= 1;
             self.cols[i] <<= 1;
@@ -43,6 +64,7 @@ impl Pattern {
             }
         }
         self.rows.push(row);
+        Ok(())
     }
 
     fn flip(&mut self, i: usize, j: usize) {
@@ -51,11 +73,12 @@ impl Pattern {
         self.cols[j] ^= 1 << (m - 1 - i);
     }
 
-    fn score(&mut self, original: bool) -> usize {
+    /// Summarized reflection lines; with `original == false` after fixing the smudge.
+    pub fn score(&mut self, original: bool) -> Result<usize> {
         let row = reflect(&self.rows, 0);
         let col = reflect(&self.cols, 0);
         if original {
-            return 100 * row + col;
+            return Ok(100 * row + col);
         }
         for i in 0..self.rows.len() {
             for j in 0..self.cols.len() {
@@ -63,34 +86,36 @@ impl Pattern {
                 let score = 100 * reflect(&self.rows, row) + reflect(&self.cols, col);
                 self.flip(i, j);
                 if score > 0 {
-                    return score;
+                    return Ok(score);
                 }
             }
         }
-        panic!("Alternate score not found!");
+        Err(Error::new("No smudge gives a new reflection line"))
     }
 }
 
-fn solve(path: &str, original: bool) {
-    let mut sum = 0;
-    let mut pat = Pattern::new();
-    for line in io::BufReader::new(File::open(path).unwrap()).lines() {
-        let line = line.unwrap();
-        if line.is_empty() {
-            sum += pat.score(original);
-            pat = Pattern::new();
-        } else {
-            pat.append(line.as_bytes());
-        }
-    }
-    sum += pat.score(original);
-    println!("{sum}");
+/// Reads the patterns separated by blank lines.
+fn parse(text: &str) -> Result<Vec<Pattern>> {
+    input::blocks(text)
+        .iter()
+        .map(|block| {
+            let mut pat = Pattern::new();
+            block.parse_lines(|line| pat.append(line))?;
+            Ok(pat)
+        })
+        .collect()
+}
+
+fn solve(text: &str, original: bool) -> Result<usize> {
+    let mut pats = parse(text)?;
+    bench::parsed();
+    pats.iter_mut().map(|pat| pat.score(original)).sum()
 }
 
-pub fn part1(path: &str) {
-    solve(path, true);
+pub fn part1(text: &str) -> Result<Answer> {
+    Ok(solve(text, true)?.into())
 }
 
-pub fn part2(path: &str) {
-    solve(path, false);
+pub fn part2(text: &str) -> Result<Answer> {
+    Ok(solve(text, false)?.into())
 }
@@ -1,92 +1,98 @@
+use crate::bench;
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::render::{self, Scene};
+use crate::Answer;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Platform {
-    map: Vec<Vec<u8>>,
-    m: usize,
-    n: usize,
+/// Platform with round (`O`) and cube-shaped (`#`) rocks.
+pub struct Platform {
+    pub map: Grid<u8>,
 }
 
 impl Platform {
-    fn read(path: &str) -> Platform {
-        let map: Vec<Vec<u8>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| line.bytes().collect())
-            .collect();
-        let (m, n) = (map.len(), map[0].len());
-        Platform { map, m, n }
+    /// Parses the platform.
+    pub fn parse(text: &str) -> Result<Platform> {
+        let map = Grid::parse(text, b".#O")?;
+        Ok(Platform { map })
     }
 
-    fn tilt<U, V>(&mut self, u: usize, v: usize, ut: U, vt: V)
-    where
-        U: Fn(usize, usize) -> usize,
-        V: Fn(usize, usize) -> usize,
-    {
-        let mut place = vec![0; v];
-        for x in 0..u {
-            for y in 0..v {
-                let (i0, j0) = (ut(x, y), vt(x, y));
-                let (i1, j1) = (ut(place[y], y), vt(place[y], y));
-                match self.map[i0][j0] {
+    /// Tilts the platform north.
+    pub fn tilt(&mut self) {
+        for j in 0..self.map.width() {
+            let mut place = 0;
+            for i in 0..self.map.height() {
+                match self.map[(i, j)] {
                     b'O' => {
-                        self.map[i0][j0] = b'.';
-                        self.map[i1][j1] = b'O';
-                        place[y] += 1;
+                        self.map[(i, j)] = b'.';
+                        self.map[(place, j)] = b'O';
+                        place += 1;
                     }
-                    b'#' => place[y] = x + 1,
+                    b'#' => place = i + 1,
                     _ => (),
                 }
             }
         }
     }
 
-    fn cycle(&mut self) {
-        let (m, n) = (self.m, self.n);
-        self.tilt(m, n, |i, _| i, |_, j| j); // north
-        self.tilt(n, m, |_, i| i, |j, _| j); // west
-        self.tilt(m, n, |i, _| m - 1 - i, |_, j| j); // south
-        self.tilt(n, m, |_, i| i, |j, _| n - 1 - j); // east
-    }
-
-    fn key(&self) -> String {
-        String::from_utf8(self.map.concat()).unwrap()
+    /// Tilts the platform north, west, south and east.
+    pub fn cycle(&mut self) {
+        for _ in 0..4 {
+            self.tilt();
+            // The next edge to tilt towards becomes the north edge.
+            self.map = self.map.rotated_cw();
+        }
     }
 
-    fn load(&self) -> usize {
+    /// Total load on the north support beams.
+    pub fn load(&self) -> usize {
+        let m = self.map.height();
         self.map
-            .iter()
+            .rows()
             .enumerate()
-            .map(|(i, r)| (self.m - i) * r.iter().filter(|&&b| b == b'O').count())
+            .map(|(i, r)| (m - i) * r.iter().filter(|&&b| b == b'O').count())
             .sum()
     }
 }
 
-pub fn part1(path: &str) {
-    let mut platform = Platform::read(path);
-    platform.tilt(platform.m, platform.n, |i, _| i, |_, j| j);
-    println!("{}", platform.load());
+pub fn part1(text: &str) -> Result<Answer> {
+    let mut platform = Platform::parse(text)?;
+    bench::parsed();
+    platform.tilt();
+    Ok(platform.load().into())
 }
 
-pub fn part2(path: &str) {
-    let mut platform = Platform::read(path);
-    let mut platform_step: HashMap<String, usize> = HashMap::new();
+pub fn part2(text: &str) -> Result<Answer> {
+    let mut platform = Platform::parse(text)?;
+    bench::parsed();
+    let mut platform_step: HashMap<Grid<u8>, usize> = HashMap::new();
     let mut step = 0;
     let mut num_steps = 1_000_000_000;
     let mut cycle_len = 0;
     while step < num_steps {
         if cycle_len == 0 {
-            let key = platform.key();
-            if platform_step.contains_key(&key) {
-                cycle_len = step - platform_step[&key];
+            if let Some(&prev_step) = platform_step.get(&platform.map) {
+                cycle_len = step - prev_step;
                 num_steps = step + (num_steps - step) % cycle_len;
             } else {
-                platform_step.insert(key, step);
+                platform_step.insert(platform.map.clone(), step);
             }
         }
         platform.cycle();
         step += 1;
     }
-    println!("{}", platform.load());
+    Ok(platform.load().into())
+}
+
+/// Platform tilted north with the round rocks highlighted.
+pub fn render(text: &str) -> Result<Scene> {
+    let mut platform = Platform::parse(text)?;
+    platform.tilt();
+    let rocks: Vec<_> = platform
+        .map
+        .iter()
+        .filter(|&(_, &b)| b == b'O')
+        .map(|(p, _)| p)
+        .collect();
+    Ok(Scene::new(platform.map).layer(render::YELLOW, rocks))
 }
@@ -1,40 +1,44 @@
+use crate::bench;
+use crate::error::{Context, Result};
+use crate::input;
+use crate::Answer;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-fn hash(s: &str) -> usize {
+/// Holiday ASCII String Helper algorithm.
+pub fn hash(s: &str) -> usize {
     s.bytes()
         .fold(0, |hash, b| (17 * (hash + b as usize)) % 256)
 }
 
-pub fn part1(path: &str) {
-    let reader = io::BufReader::new(File::open(path).unwrap());
-    let line = reader.lines().next().unwrap().unwrap();
-    let sum: usize = line.split(',').map(|s| hash(s)).sum();
-    println!("{sum}");
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
 }
 
-pub fn part2(path: &str) {
-    let reader = io::BufReader::new(File::open(path).unwrap());
-    let line = reader.lines().next().unwrap().unwrap();
+pub fn part1(text: &str) -> Result<Answer> {
+    let line = first_line(text);
+    bench::parsed();
+    let sum: usize = line.split(',').map(hash).sum();
+    Ok(sum.into())
+}
+
+pub fn part2(text: &str) -> Result<Answer> {
+    let line = first_line(text);
+    bench::parsed();
     let mut box_contents: Vec<Vec<Option<usize>>> = vec![vec![]; 256];
     let mut label_to_idx: HashMap<String, usize> = HashMap::new();
     for cmd in line.split(',') {
-        if cmd.ends_with('-') {
-            let label = &cmd[..cmd.len() - 1];
-            let label_hash = hash(&label);
+        if let Some(label) = cmd.strip_suffix('-') {
+            let label_hash = hash(label);
             if label_to_idx.contains_key(label) {
                 let i = label_to_idx[label];
                 box_contents[label_hash][i] = None;
                 label_to_idx.remove(label);
             }
         } else {
-            let mut cmd_parts = cmd.split('=');
-            let (label, focal_length) = (
-                cmd_parts.next().unwrap(),
-                cmd_parts.next().unwrap().parse().unwrap(),
-            );
-            let label_hash = hash(&label);
+            let (label, focal_length) = input::split_once(line, cmd, "=")
+                .and_then(|(label, len)| Ok((label, input::parse(line, len)?)))
+                .line(1)?;
+            let label_hash = hash(label);
             if label_to_idx.contains_key(label) {
                 let i = label_to_idx[label];
                 box_contents[label_hash][i] = Some(focal_length);
@@ -57,5 +61,5 @@ pub fn part2(path: &str) {
         .enumerate()
         .map(|(box_index, sum)| (box_index + 1) * sum)
         .sum();
-    println!("{sum}");
+    Ok(sum.into())
 }
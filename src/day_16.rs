@@ -1,119 +1,122 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::Result;
+use crate::geometry::Direction;
+use crate::grid::{Grid, Pos};
+use crate::render::{self, Scene};
+use crate::Answer;
 
+/// Position and direction of a beam.
 #[derive(Clone, Copy)]
-struct Coord(usize, usize, u8);
+pub struct Beam {
+    pub pos: Pos,
+    pub dir: Direction,
+}
 
-fn update(c: Coord, visits: &mut Vec<Vec<u8>>) -> bool {
-    let visit = &mut visits[c.0][c.1];
+fn update(beam: Beam, visits: &mut Grid<u8>) -> bool {
+    let visit = &mut visits[beam.pos];
     let old_visit = *visit;
-    *visit |= match c.2 {
-        b'^' => 1,
-        b'v' => 2,
-        b'<' => 4,
-        b'>' => 8,
-        _ => panic!("Invalid direction: '{}'", c.2 as char),
-    };
+    *visit |= 1 << beam.dir as u8;
     *visit != old_visit
 }
 
-struct Contraption {
-    map: Vec<Vec<u8>>,
-    m: usize,
-    n: usize,
+/// Contraption of mirrors and splitters.
+pub struct Contraption {
+    pub map: Grid<u8>,
 }
 
 impl Contraption {
-    fn read(path: &str) -> Contraption {
-        let map: Vec<Vec<u8>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| line.bytes().collect())
-            .collect();
-        let (m, n) = (map.len(), map[0].len());
-        Contraption { map, m, n }
+    /// Parses the contraption.
+    pub fn parse(text: &str) -> Result<Contraption> {
+        let map = Grid::parse(text, b"./\\|-")?;
+        Ok(Contraption { map })
     }
 
-    fn step(&self, c: Coord) -> Vec<Coord> {
-        let mut out = Vec::with_capacity(2);
-        match (self.map[c.0][c.1], c.2) {
-            (b'.', b'^') | (b'|', b'^') | (b'\\', b'<') | (b'/', b'>') if c.0 > 0 => {
-                out.push(Coord(c.0 - 1, c.1, b'^'))
-            }
-            (b'.', b'v') | (b'|', b'v') | (b'\\', b'>') | (b'/', b'<') if c.0 < self.m - 1 => {
-                out.push(Coord(c.0 + 1, c.1, b'v'))
-            }
-            (b'.', b'<') | (b'-', b'<') | (b'\\', b'^') | (b'/', b'v') if c.1 > 0 => {
-                out.push(Coord(c.0, c.1 - 1, b'<'))
-            }
-            (b'.', b'>') | (b'-', b'>') | (b'\\', b'v') | (b'/', b'^') if c.1 < self.n - 1 => {
-                out.push(Coord(c.0, c.1 + 1, b'>'))
-            }
-            (b'|', b'<') | (b'|', b'>') => {
-                if c.0 > 0 {
-                    out.push(Coord(c.0 - 1, c.1, b'^'))
-                }
-                if c.0 < self.m - 1 {
-                    out.push(Coord(c.0 + 1, c.1, b'v'))
-                }
-            }
-            (b'-', b'^') | (b'-', b'v') => {
-                if c.1 > 0 {
-                    out.push(Coord(c.0, c.1 - 1, b'<'))
-                }
-                if c.1 < self.n - 1 {
-                    out.push(Coord(c.0, c.1 + 1, b'>'))
-                }
-            }
-            _ => (),
-        }
-        out
+    fn step(&self, beam: Beam) -> Vec<Beam> {
+        use Direction::*;
+        let dirs = match (self.map[beam.pos], beam.dir) {
+            (b'/', Up | Down) | (b'\\', Left | Right) => vec![beam.dir.turn_right()],
+            (b'/', Left | Right) | (b'\\', Up | Down) => vec![beam.dir.turn_left()],
+            (b'|', Left | Right) => vec![Up, Down],
+            (b'-', Up | Down) => vec![Left, Right],
+            _ => vec![beam.dir],
+        };
+        dirs.into_iter()
+            .filter_map(|dir| {
+                let pos = self.map.step(beam.pos, dir)?;
+                Some(Beam { pos, dir })
+            })
+            .collect()
     }
 
-    fn multi_step(&self, beams: Vec<Coord>, visits: &mut Vec<Vec<u8>>) -> Vec<Coord> {
+    fn multi_step(&self, beams: Vec<Beam>, visits: &mut Grid<u8>) -> Vec<Beam> {
         let mut out = Vec::with_capacity(2 * beams.len());
-        for c in beams {
-            if update(c, visits) {
-                out.append(&mut self.step(c));
+        for beam in beams {
+            if update(beam, visits) {
+                out.append(&mut self.step(beam));
             }
         }
         out
     }
 
-    fn explore(&self, c: Coord) -> usize {
-        let mut visits = vec![vec![0; self.n]; self.m];
-        let mut beams = vec![c];
+    /// Tiles energized by a beam entering with `beam`.
+    pub fn energized(&self, beam: Beam) -> Vec<Pos> {
+        let mut visits = self.map.map(|_| 0);
+        let mut beams = vec![beam];
         while !beams.is_empty() {
             beams = self.multi_step(beams, &mut visits);
         }
         visits
             .iter()
-            .map(|row| row.iter().filter(|&&x| x != 0).count())
-            .sum()
+            .filter(|&(_, &x)| x != 0)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// Number of tiles energized by a beam entering with `beam`.
+    pub fn explore(&self, beam: Beam) -> usize {
+        self.energized(beam).len()
     }
 
-    fn explore_all(&self) -> usize {
-        let top = (0..self.n).map(|j| Coord(0, j, b'v'));
-        let bottom = (0..self.n).map(|j| Coord(self.m - 1, j, b'^'));
-        let left = (0..self.m).map(|i| Coord(i, 0, b'>'));
-        let right = (0..self.m).map(|i| Coord(i, self.n - 1, b'<'));
+    /// Maximum number of tiles energized by a beam entering from any edge.
+    pub fn explore_all(&self) -> usize {
+        let (m, n) = (self.map.height(), self.map.width());
+        let beam = |pos, dir| Beam { pos, dir };
+        let top = (0..n).map(|j| beam((0, j), Direction::Down));
+        let bottom = (0..n).map(|j| beam((m - 1, j), Direction::Up));
+        let left = (0..m).map(|i| beam((i, 0), Direction::Right));
+        let right = (0..m).map(|i| beam((i, n - 1), Direction::Left));
         top.chain(bottom)
             .chain(left)
             .chain(right)
-            .map(|c| self.explore(c))
+            .map(|beam| self.explore(beam))
             .max()
             .unwrap()
     }
 }
 
-pub fn part1(path: &str) {
-    let contraption = Contraption::read(path);
-    let sum = contraption.explore(Coord(0, 0, b'>'));
-    println!("{sum}");
+pub fn part1(text: &str) -> Result<Answer> {
+    let contraption = Contraption::parse(text)?;
+    bench::parsed();
+    let sum = contraption.explore(Beam {
+        pos: (0, 0),
+        dir: Direction::Right,
+    });
+    Ok(sum.into())
 }
 
-pub fn part2(path: &str) {
-    let contraption = Contraption::read(path);
+pub fn part2(text: &str) -> Result<Answer> {
+    let contraption = Contraption::parse(text)?;
+    bench::parsed();
     let sum = contraption.explore_all();
-    println!("{sum}");
+    Ok(sum.into())
+}
+
+/// Contraption with the tiles energized by the beam of part 1 highlighted.
+pub fn render(text: &str) -> Result<Scene> {
+    let contraption = Contraption::parse(text)?;
+    let energized = contraption.energized(Beam {
+        pos: (0, 0),
+        dir: Direction::Right,
+    });
+    Ok(Scene::new(contraption.map).layer(render::YELLOW, energized))
 }
@@ -1,92 +1,93 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::geometry::Direction;
+use crate::grid::{Grid, Pos};
+use crate::search;
+use crate::Answer;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Coord(usize, usize, u8, u8, u8, u8);
+/// Position of a crucible, the direction it last moved in and how many blocks it
+/// has moved in a straight line.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Crucible {
+    pos: Pos,
+    dir: Direction,
+    run: u8,
+}
 
-struct City {
-    map: Vec<Vec<u32>>,
-    m: usize,
-    n: usize,
+/// City map of heat losses.
+pub struct City {
+    pub map: Grid<u32>,
 }
 
 impl City {
-    fn read(path: &str) -> City {
-        let map: Vec<Vec<u32>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| line.bytes().map(|b| (b - b'0') as u32).collect())
-            .collect();
-        let (m, n) = (map.len(), map[0].len());
-        City { map, m, n }
+    /// Parses the city map.
+    pub fn parse(text: &str) -> Result<City> {
+        let map = Grid::parse(text, b"0123456789")?.map(|b| (b - b'0') as u32);
+        Ok(City { map })
     }
 
-    fn edges(&self, c: Coord, ultra: bool) -> Vec<Coord> {
-        let mut out = Vec::with_capacity(4);
-        if c.0 > 0 && c.2 > 0 {
-            if ultra {
-                let side = if c.2 - 1 <= 6 { 10 } else { 0 };
-                out.push(Coord(c.0 - 1, c.1, c.2 - 1, 0, side, side));
-            } else {
-                out.push(Coord(c.0 - 1, c.1, c.2 - 1, 0, 3, 3));
-            }
-        }
-        if c.0 < self.m - 1 && c.3 > 0 {
-            if ultra {
-                let side = if c.3 - 1 <= 6 { 10 } else { 0 };
-                out.push(Coord(c.0 + 1, c.1, 0, c.3 - 1, side, side));
-            } else {
-                out.push(Coord(c.0 + 1, c.1, 0, c.3 - 1, 3, 3));
-            }
+    /// Minimum and maximum number of blocks moved before turning.
+    fn limits(ultra: bool) -> (u8, u8) {
+        if ultra {
+            (4, 10)
+        } else {
+            (1, 3)
         }
-        if c.1 > 0 && c.4 > 0 {
-            if ultra {
-                let side = if c.4 - 1 <= 6 { 10 } else { 0 };
-                out.push(Coord(c.0, c.1 - 1, side, side, c.4 - 1, 0));
-            } else {
-                out.push(Coord(c.0, c.1 - 1, 3, 3, c.4 - 1, 0));
-            }
+    }
+
+    fn edges(&self, c: Crucible, ultra: bool) -> Vec<Crucible> {
+        let (min, max) = City::limits(ultra);
+        let mut out = Vec::with_capacity(3);
+        if c.run < max {
+            out.push((c.dir, c.run + 1));
         }
-        if c.1 < self.n - 1 && c.5 > 0 {
-            if ultra {
-                let side = if c.5 - 1 <= 6 { 10 } else { 0 };
-                out.push(Coord(c.0, c.1 + 1, side, side, 0, c.5 - 1));
-            } else {
-                out.push(Coord(c.0, c.1 + 1, 3, 3, 0, c.5 - 1));
-            }
+        if c.run >= min {
+            out.push((c.dir.turn_left(), 1));
+            out.push((c.dir.turn_right(), 1));
         }
-        out
+        out.into_iter()
+            .filter_map(|(dir, run)| {
+                let pos = self.map.step(c.pos, dir)?;
+                Some(Crucible { pos, dir, run })
+            })
+            .collect()
     }
 
-    fn search(&self, ultra: bool) -> u32 {
-        let mut visited: HashSet<Coord> = HashSet::new();
-        let mut heap = BinaryHeap::new();
-        heap.push((
-            Reverse(0),
-            Coord(0, 0, 0, ultra as u8 * 7 + 3, 0, ultra as u8 * 7 + 3),
-        ));
-        while let Some((Reverse(c0_dist), c0)) = heap.pop() {
-            if !visited.insert(c0) {
-                continue;
-            }
-            if c0.0 == self.m - 1 && c0.1 == self.n - 1 {
-                return c0_dist;
-            }
-            for c1 in self.edges(c0, ultra) {
-                heap.push((Reverse(c0_dist + self.map[c1.0][c1.1]), c1));
-            }
-        }
-        0
+    /// Least heat loss from the top-left to the bottom-right block, using the
+    /// ultra crucible if `ultra` is set.
+    pub fn search(&self, ultra: bool) -> Result<u32> {
+        let (min, _) = City::limits(ultra);
+        let end = (self.map.height() - 1, self.map.width() - 1);
+        // Every remaining block costs at least the cheapest tile, which may be 0.
+        let min_cost = self.map.iter().map(|(_, &cost)| cost).min().unwrap_or(0);
+        let starts = [Direction::Right, Direction::Down].map(|dir| Crucible {
+            pos: (0, 0),
+            dir,
+            run: 0,
+        });
+        search::astar(
+            starts,
+            |&c0| {
+                self.edges(c0, ultra)
+                    .into_iter()
+                    .map(|c1| (c1, self.map[c1.pos]))
+            },
+            |c| (end.0 - c.pos.0 + end.1 - c.pos.1) as u32 * min_cost,
+            |c| c.pos == end && c.run >= min,
+        )
+        .target_distance()
+        .ok_or_else(|| Error::new("No path to the bottom-right block"))
     }
 }
 
-pub fn part1(path: &str) {
-    println!("{}", City::read(path).search(false));
+pub fn part1(text: &str) -> Result<Answer> {
+    let city = City::parse(text)?;
+    bench::parsed();
+    Ok(city.search(false)?.into())
 }
 
-pub fn part2(path: &str) {
-    println!("{}", City::read(path).search(true));
+pub fn part2(text: &str) -> Result<Answer> {
+    let city = City::parse(text)?;
+    bench::parsed();
+    Ok(city.search(true)?.into())
 }
@@ -1,109 +1,160 @@
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::input;
+use crate::Answer;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
 
+/// Dig plan as a list of edges, each from its top or left end to its bottom or right end.
 #[derive(Debug)]
-struct Lagoon {
-    edges: Vec<(i64, i64, i64, i64)>,
+pub struct Lagoon {
+    pub edges: Vec<(Point, Point)>,
 }
 
 impl Lagoon {
-    fn read(path: &str, parse_hex: bool) -> Lagoon {
+    /// Parses the dig plan, decoding the hex color codes if `parse_hex` is set. The plan
+    /// must turn left or right at every step and end where it started.
+    pub fn parse(text: &str, parse_hex: bool) -> Result<Lagoon> {
+        let steps = input::parse_lines(text, |line| Lagoon::parse_step(line, parse_hex))?;
+        if steps.is_empty() {
+            return Err(Error::new("Empty dig plan"));
+        }
         let mut edges = vec![];
-        let mut i = 0i64;
-        let mut j = 0i64;
-        for line in io::BufReader::new(File::open(path).unwrap()).lines() {
-            let line = line.unwrap();
-            let parts: Vec<_> = line.split(' ').collect();
-            let dir: u8;
-            let len: i64;
-            if !parse_hex {
-                dir = parts[0].as_bytes()[0];
-                len = parts[1].parse().unwrap();
-            } else {
-                dir = match parts[2].as_bytes()[7] {
-                    b'0' => b'R',
-                    b'1' => b'D',
-                    b'2' => b'L',
-                    b'3' => b'U',
-                    _ => panic!("Invalid hex code: '{}'", &parts[2][2..8]),
-                };
-                len = i64::from_str_radix(&parts[2][2..7], 16).unwrap();
+        let mut p = Point::default();
+        for &(dir, len) in steps.iter() {
+            let q = p.step_by(dir, len);
+            edges.push((p.min(q), p.max(q)));
+            p = q;
+        }
+        if p != Point::default() {
+            return Err(Error::new(format!(
+                "Dig plan ends at ({}, {}) instead of the starting point",
+                p.i, p.j
+            )))
+            .line(steps.len());
+        }
+        // The step before the first one is the last one, which leads back to the start.
+        for (k, &(dir, _)) in steps.iter().enumerate() {
+            let prev = steps[(k + steps.len() - 1) % steps.len()].0;
+            if dir == prev {
+                return Err(Error::new("Step keeps the direction of the one before")).line(k + 1);
+            }
+            if dir == prev.reverse() {
+                return Err(Error::new("Step doubles back on the one before")).line(k + 1);
             }
-            edges.push(match dir {
-                b'U' => {
-                    i -= len;
-                    (i, j, i + len, j)
-                }
-                b'D' => {
-                    i += len;
-                    (i - len, j, i, j)
-                }
-                b'L' => {
-                    j -= len;
-                    (i, j, i, j + len)
-                }
-                b'R' => {
-                    j += len;
-                    (i, j - len, i, j)
-                }
-                _ => panic!("Invalid direction: '{}'", dir as char),
-            });
         }
-        Lagoon { edges }
+        Ok(Lagoon { edges })
     }
 
-    fn lava_at_row(&self, i: i64) -> i64 {
+    /// Parses a step such as `R 6 (#70c710)` into its direction and length.
+    fn parse_step(line: &str, parse_hex: bool) -> Result<(Direction, i64)> {
+        let parts: Vec<_> = line.split_ascii_whitespace().collect();
+        if parts.len() != 3 {
+            return Err(Error::at(line, line, "Expected '<dir> <len> (#<hex>)'"));
+        }
+        if !parse_hex {
+            let mut chars = parts[0].chars();
+            let dir = chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .and_then(Direction::from_letter)
+                .ok_or_else(|| {
+                    Error::at(line, parts[0], format!("Invalid direction: '{}'", parts[0]))
+                })?;
+            let len = input::parse(line, parts[1])?;
+            if len <= 0 {
+                return Err(Error::at(line, parts[1], format!("Invalid length: {len}")));
+            }
+            Ok((dir, len))
+        } else {
+            let hex = parts[2]
+                .strip_prefix("(#")
+                .and_then(|s| s.strip_suffix(')'))
+                .filter(|s| s.len() == 6 && s.bytes().all(|b| b.is_ascii_hexdigit()))
+                .ok_or_else(|| {
+                    Error::at(line, parts[2], format!("Invalid hex code: '{}'", parts[2]))
+                })?;
+            let dir = match hex.as_bytes()[5] {
+                b'0' => Direction::Right,
+                b'1' => Direction::Down,
+                b'2' => Direction::Left,
+                b'3' => Direction::Up,
+                _ => return Err(Error::at(line, hex, format!("Invalid hex code: '{hex}'"))),
+            };
+            let len = i64::from_str_radix(&hex[..5], 16).unwrap();
+            if len == 0 {
+                return Err(Error::at(line, &hex[..5], "Invalid length: 0"));
+            }
+            Ok((dir, len))
+        }
+    }
+
+    /// Number of cubic meters of lava in row `i`.
+    pub fn lava_at_row(&self, i: i64) -> Result<i64> {
+        let crossing = || Error::new(format!("Dig plan crosses itself in row {i}"));
+        let vertical = |e: &(Point, Point)| e.0.j == e.1.j;
         let mut e: Vec<_> = self
             .edges
             .iter()
-            .filter(|e| e.0 <= i && e.2 >= i)
-            .map(|&e| e)
+            .filter(|e| e.0.i <= i && e.1.i >= i)
+            .copied()
             .collect();
-        e.sort_by_key(|e| (e.1, e.3));
+        e.sort_by_key(|e| (e.0.j, e.1.j));
         let mut sum = 0;
         let mut inside = false;
         let mut k = 0;
         while k < e.len() {
-            assert_eq!(e[k].1, e[k].3); // Vertical line
-            let (u, d) = (e[k].0, e[k].2);
-            if k < e.len() - 1 && e[k + 1].1 < e[k + 1].3 {
-                sum += e[k + 1].3 - e[k + 1].1;
+            if !vertical(&e[k]) {
+                return Err(crossing());
+            }
+            let (u, d) = (e[k].0.i, e[k].1.i);
+            if k < e.len() - 1 && !vertical(&e[k + 1]) {
+                sum += e[k + 1].1.j - e[k + 1].0.j;
                 k += 2; // Skip horizontal line (should be at most one)
             }
-            assert_eq!(e[k].1, e[k].3); // Vertical line
-            let (u, d) = (e[k].0.min(u), e[k].2.max(d));
+            if !e.get(k).is_some_and(vertical) {
+                return Err(crossing());
+            }
+            let (u, d) = (e[k].0.i.min(u), e[k].1.i.max(d));
             if u < i && i < d {
                 inside = !inside;
             }
             sum += 1;
             k += 1;
             if inside {
-                sum += e[k].1 - e[k - 1].1 - 1; // Add inside empty space
+                let next = e.get(k).ok_or_else(crossing)?;
+                sum += next.0.j - e[k - 1].0.j - 1; // Add inside empty space
             }
         }
-        sum
+        Ok(sum)
     }
 
-    fn lava(&self) -> i64 {
+    /// Total number of cubic meters of lava the lagoon can hold.
+    pub fn lava(&self) -> Result<i64> {
         let rows: HashSet<_> = self
             .edges
             .iter()
-            .flat_map(|e| vec![e.0 - 1, e.0, e.0 + 1, e.2 - 1, e.2, e.2 + 1])
+            .flat_map(|e| vec![e.0.i - 1, e.0.i, e.0.i + 1, e.1.i - 1, e.1.i, e.1.i + 1])
             .collect();
         let mut rows: Vec<_> = rows.into_iter().collect();
         rows.sort();
-        rows.push(rows.last().unwrap() + 1);
+        if let Some(&last) = rows.last() {
+            rows.push(last + 1);
+        }
         rows.windows(2)
-            .map(|r| (r[1] - r[0]) * self.lava_at_row(r[0]))
+            .map(|r| Ok((r[1] - r[0]) * self.lava_at_row(r[0])?))
             .sum()
     }
 }
 
-pub fn part1(path: &str) {
-    println!("{}", Lagoon::read(path, false).lava());
+pub fn part1(text: &str) -> Result<Answer> {
+    let lagoon = Lagoon::parse(text, false)?;
+    bench::parsed();
+    Ok(lagoon.lava()?.into())
 }
 
-pub fn part2(path: &str) {
-    println!("{}", Lagoon::read(path, true).lava());
+pub fn part2(text: &str) -> Result<Answer> {
+    let lagoon = Lagoon::parse(text, true)?;
+    bench::parsed();
+    Ok(lagoon.lava()?.into())
 }
@@ -1,42 +1,68 @@
+use crate::bench;
+use crate::error::{Context, Error, Result};
+use crate::input;
+use crate::interval::{Interval, IntervalBox};
+use crate::Answer;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Condition {
-    cat: usize,
-    cmp: Ordering,
-    val: usize,
+/// Condition such as `a<2006` on one of the `xmas` categories.
+pub struct Condition {
+    pub cat: usize,
+    pub cmp: Ordering,
+    pub val: u64,
 }
 
 impl Condition {
-    fn parse(c: &str) -> Condition {
-        let b = c.as_bytes();
-        let cat = match b[0] {
-            b'x' => 0,
-            b'm' => 1,
-            b'a' => 2,
-            b's' => 3,
-            _ => panic!("Invalid category: '{}'", b[0] as char),
+    pub fn parse(c: &str) -> Result<Condition> {
+        let invalid = || Error::at(c, c, format!("Invalid condition: '{c}'"));
+        let cat = match c.chars().next().ok_or_else(invalid)? {
+            'x' => 0,
+            'm' => 1,
+            'a' => 2,
+            's' => 3,
+            other => return Err(Error::at(c, c, format!("Invalid category: '{other}'"))),
         };
-        let cmp = match b[1] {
-            b'<' => Ordering::Less,
-            b'>' => Ordering::Greater,
-            _ => panic!("Invalid ordering: '{}'", b[1] as char),
+        if c.len() < 3 || !c.is_char_boundary(2) {
+            return Err(invalid());
+        }
+        let cmp = match &c[1..2] {
+            "<" => Ordering::Less,
+            ">" => Ordering::Greater,
+            other => {
+                return Err(Error::at(
+                    c,
+                    &c[1..],
+                    format!("Invalid ordering: '{other}'"),
+                ))
+            }
         };
-        let val = c[2..].parse().unwrap();
-        Condition { cat, cmp, val }
+        let val = input::parse(c, &c[2..])?;
+        Ok(Condition { cat, cmp, val })
+    }
+
+    /// Splits the box into the ratings satisfying the condition and the others.
+    pub fn split(&self, ins: &IntervalBox<4>) -> (IntervalBox<4>, IntervalBox<4>) {
+        match self.cmp {
+            Ordering::Less => ins.split_at(self.cat, self.val),
+            Ordering::Greater => {
+                let (lo, hi) = ins.split_at(self.cat, self.val + 1);
+                (hi, lo)
+            }
+            _ => panic!("Invalid condition"),
+        }
     }
 }
 
-enum Outcome {
+/// Outcome of a matching rule.
+pub enum Outcome {
     Redirect(String),
     Accept,
     Reject,
 }
 
 impl Outcome {
-    fn parse(o: &str) -> Outcome {
+    pub fn parse(o: &str) -> Outcome {
         match o {
             "A" => Outcome::Accept,
             "R" => Outcome::Reject,
@@ -45,27 +71,29 @@ impl Outcome {
     }
 }
 
-struct Rule {
-    con: Option<Condition>,
-    out: Outcome,
+/// Rule with an optional condition.
+pub struct Rule {
+    pub con: Option<Condition>,
+    pub out: Outcome,
 }
 
 impl Rule {
-    fn parse(r: &str) -> Rule {
+    pub fn parse(r: &str) -> Result<Rule> {
         if let Some(i) = r.find(':') {
-            Rule {
-                con: Some(Condition::parse(&r[..i])),
+            Ok(Rule {
+                con: Some(Condition::parse(&r[..i])?),
                 out: Outcome::parse(&r[i + 1..]),
-            }
+            })
         } else {
-            Rule {
+            Ok(Rule {
                 con: None,
                 out: Outcome::parse(r),
-            }
+            })
         }
     }
 
-    fn matches(&self, p: &Part) -> bool {
+    /// Whether the rule applies to the part.
+    pub fn matches(&self, p: &Part) -> bool {
         if let Some(con) = self.con.as_ref() {
             match con.cmp {
                 Ordering::Less => p.0[con.cat] < con.val,
@@ -78,19 +106,28 @@ impl Rule {
     }
 }
 
-struct Rules(Vec<Rule>);
+/// Rules of a single workflow.
+pub struct Rules(pub Vec<Rule>);
 
 impl Rules {
-    fn parse(r: &str) -> Rules {
-        Rules(
-            r[1..r.len() - 1]
-                .split(',')
-                .map(|r| Rule::parse(r))
-                .collect(),
-        )
+    /// Parses the rules such as `{a<2006:qkq,m>2090:A,rfg}`.
+    pub fn parse(r: &str) -> Result<Rules> {
+        let inner = r
+            .strip_prefix('{')
+            .and_then(|r| r.strip_suffix('}'))
+            .ok_or_else(|| Error::at(r, r, "Expected '{<rules>}'"))?;
+        let rules = inner
+            .split(',')
+            .map(|s| Rule::parse(s).within(r, s))
+            .collect::<Result<Vec<_>>>()?;
+        if rules.last().unwrap().con.is_some() {
+            return Err(Error::at(r, r, "Last rule must not have a condition"));
+        }
+        Ok(Rules(rules))
     }
 
-    fn run(&self, p: &Part) -> &Outcome {
+    /// Outcome of the first matching rule.
+    pub fn run(&self, p: &Part) -> &Outcome {
         for rule in self.0.iter() {
             if rule.matches(p) {
                 return &rule.out;
@@ -100,19 +137,67 @@ impl Rules {
     }
 }
 
-struct Workflows(HashMap<String, Rules>);
+/// Workflows by name.
+#[derive(Default)]
+pub struct Workflows(pub HashMap<String, Rules>);
 
 impl Workflows {
-    fn new() -> Workflows {
+    pub fn new() -> Workflows {
         Workflows(HashMap::new())
     }
 
-    fn add(&mut self, w: &str) {
-        let i = w.find('{').unwrap();
-        self.0.insert(String::from(&w[..i]), Rules::parse(&w[i..]));
+    /// Parses and adds a workflow such as `px{a<2006:qkq,m>2090:A,rfg}`.
+    pub fn add(&mut self, w: &str) -> Result<()> {
+        let i = w
+            .find('{')
+            .ok_or_else(|| Error::at(w, w, "Expected '<name>{<rules>}'"))?;
+        let rules = Rules::parse(&w[i..]).within(w, &w[i..])?;
+        self.0.insert(String::from(&w[..i]), rules);
+        Ok(())
     }
 
-    fn run(&self, p: &Part) -> &Outcome {
+    /// Checks that the `in` workflow and all redirect targets exist, and that no
+    /// workflow reachable from `in` leads back to itself.
+    fn check(&self) -> Result<()> {
+        if !self.0.contains_key("in") {
+            return Err(Error::new("Missing workflow 'in'"));
+        }
+        for (name, rules) in self.0.iter() {
+            for rule in rules.0.iter() {
+                if let Outcome::Redirect(next) = &rule.out {
+                    if !self.0.contains_key(next) {
+                        return Err(Error::new(format!(
+                            "Unknown workflow '{next}' in workflow '{name}'"
+                        )));
+                    }
+                }
+            }
+        }
+        self.check_cycles("in", &mut HashMap::new())
+    }
+
+    /// Depth-first search for a cycle of redirects from `cur`; `seen` maps the workflows
+    /// visited so far to whether their search is finished.
+    fn check_cycles<'a>(&'a self, cur: &'a str, seen: &mut HashMap<&'a str, bool>) -> Result<()> {
+        match seen.get(cur) {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                return Err(Error::new(format!("Workflow '{cur}' leads back to itself")))
+            }
+            None => {}
+        }
+        seen.insert(cur, false);
+        for rule in self.0[cur].0.iter() {
+            if let Outcome::Redirect(next) = &rule.out {
+                self.check_cycles(next, seen)?;
+            }
+        }
+        seen.insert(cur, true);
+        Ok(())
+    }
+
+    /// Runs the part through the workflows starting at `in`.
+    pub fn run(&self, p: &Part) -> &Outcome {
         let mut cur = "in";
         loop {
             let out = self.0[cur].run(p);
@@ -124,140 +209,92 @@ impl Workflows {
         }
     }
 
-    fn solve(&self, cur: &str, ins: Intervals) -> u64 {
+    /// Number of combinations in `ins` accepted starting from workflow `cur`.
+    pub fn solve(&self, cur: &str, ins: IntervalBox<4>) -> u128 {
         let mut ins = ins;
         let mut sum = 0;
         for rule in &self.0[cur].0 {
-            if ins.volume() == 0 {
+            if ins.is_empty() {
                 break;
             }
-            match &rule.out {
-                Outcome::Redirect(next) => {
-                    if let Some(con) = rule.con.as_ref() {
-                        sum += self.solve(&next, ins.restrict(con, false));
-                        ins = ins.restrict(con, true);
-                    } else {
-                        sum += self.solve(&next, ins);
-                    }
-                }
-                Outcome::Accept => {
-                    if let Some(con) = rule.con.as_ref() {
-                        sum += ins.restrict(con, false).volume();
-                        ins = ins.restrict(con, true);
-                    } else {
-                        sum += ins.volume();
-                    }
-                }
-                Outcome::Reject => {
-                    if let Some(con) = rule.con.as_ref() {
-                        ins = ins.restrict(con, true);
-                    }
-                }
-            }
+            let (matched, rest) = match rule.con.as_ref() {
+                Some(con) => con.split(&ins),
+                None => (ins, IntervalBox([Interval::new(0, 0); 4])),
+            };
+            sum += match &rule.out {
+                Outcome::Redirect(next) => self.solve(next, matched),
+                Outcome::Accept => matched.volume(),
+                Outcome::Reject => 0,
+            };
+            ins = rest;
         }
         sum
     }
 }
 
-struct Part([usize; 4]);
+/// Part ratings in `xmas` order.
+pub struct Part(pub [u64; 4]);
 
 impl Part {
-    fn parse(p: &str) -> Part {
-        let cats: Vec<_> = p[1..p.len() - 1].split(',').collect();
-        assert_eq!(cats.len(), 4);
-        Part([
-            cats[0][2..].parse().unwrap(),
-            cats[1][2..].parse().unwrap(),
-            cats[2][2..].parse().unwrap(),
-            cats[3][2..].parse().unwrap(),
-        ])
-    }
-}
-
-#[derive(Clone, Copy)]
-struct Interval(usize, usize);
-
-impl Interval {
-    fn new() -> Interval {
-        Interval(1, 4001)
-    }
-
-    fn len(&self) -> usize {
-        self.1 - self.0
-    }
-
-    fn restrict(&self, cmp: Ordering, val: usize, inverted: bool) -> Interval {
-        if inverted {
-            match cmp {
-                Ordering::Less => Interval(self.0.max(val).min(self.1), self.1),
-                Ordering::Greater => Interval(self.0, self.1.min(val + 1).max(self.0)),
-                _ => panic!("Invalid condition"),
-            }
-        } else {
-            match cmp {
-                Ordering::Less => Interval(self.0, self.1.min(val).max(self.0)),
-                Ordering::Greater => Interval(self.0.max(val + 1).min(self.1), self.1),
-                _ => panic!("Invalid condition"),
-            }
+    /// Parses a part such as `{x=787,m=2655,a=1222,s=2876}`.
+    pub fn parse(p: &str) -> Result<Part> {
+        let inner = p
+            .strip_prefix('{')
+            .and_then(|p| p.strip_suffix('}'))
+            .ok_or_else(|| Error::at(p, p, "Expected '{x=<x>,m=<m>,a=<a>,s=<s>}'"))?;
+        let cats: Vec<_> = inner.split(',').collect();
+        if cats.len() != 4 {
+            return Err(Error::at(
+                p,
+                inner,
+                format!("Expected 4 ratings, found {}", cats.len()),
+            ));
+        }
+        let mut out = [0; 4];
+        for (i, (cat, prefix)) in cats.iter().zip(["x=", "m=", "a=", "s="]).enumerate() {
+            out[i] = input::parse(p, input::expect_prefix(cat, prefix).within(p, cat)?)?;
         }
+        Ok(Part(out))
     }
 }
 
-#[derive(Clone, Copy)]
-struct Intervals([Interval; 4]);
-
-impl Intervals {
-    fn new() -> Intervals {
-        Intervals([
-            Interval::new(),
-            Interval::new(),
-            Interval::new(),
-            Interval::new(),
-        ])
-    }
-
-    fn volume(&self) -> u64 {
-        self.0.iter().map(|int| int.len() as u64).product()
-    }
-
-    fn restrict(&self, con: &Condition, inverted: bool) -> Intervals {
-        let mut out = self.clone();
-        out.0[con.cat] = out.0[con.cat].restrict(con.cmp, con.val, inverted);
-        out
-    }
+/// Box of all possible ratings in `xmas` order.
+pub fn all_ratings() -> IntervalBox<4> {
+    IntervalBox([Interval::new(1, 4001); 4])
 }
 
-pub fn part1(path: &str) {
-    let mut lines = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok);
-    let mut ws = Workflows::new();
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
+/// Parses the workflows and the part ratings.
+pub fn parse(text: &str) -> Result<(Workflows, Vec<Part>)> {
+    let blocks = input::blocks(text);
+    let (workflows, parts) = match blocks.as_slice() {
+        [workflows] => (workflows, vec![]),
+        [workflows, parts] => (workflows, parts.parse_lines(Part::parse)?),
+        _ => {
+            return Err(Error::new(
+                "Expected the workflows and the parts separated by a blank line",
+            ))
         }
-        ws.add(&line);
-    }
-    let ps: Vec<_> = lines.map(|line| Part::parse(&line)).collect();
+    };
+    let mut ws = Workflows::new();
+    workflows.parse_lines(|line| ws.add(line))?;
+    ws.check()?;
+    Ok((ws, parts))
+}
+
+pub fn part1(text: &str) -> Result<Answer> {
+    let (ws, ps) = parse(text)?;
+    bench::parsed();
     let sum = ps
         .iter()
         .filter(|&p| matches!(ws.run(p), Outcome::Accept))
-        .map(|p| p.0.iter().sum::<usize>())
-        .sum::<usize>();
-    println!("{sum}");
+        .map(|p| p.0.iter().sum::<u64>())
+        .sum::<u64>();
+    Ok(sum.into())
 }
 
-pub fn part2(path: &str) {
-    let mut lines = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok);
-    let mut ws = Workflows::new();
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
-        }
-        ws.add(&line);
-    }
-    let sum = ws.solve("in", Intervals::new());
-    println!("{sum}");
+pub fn part2(text: &str) -> Result<Answer> {
+    let (ws, _) = parse(text)?;
+    bench::parsed();
+    let sum = ws.solve("in", all_ratings());
+    Ok(sum.into())
 }
@@ -1,8 +1,11 @@
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::math;
+use crate::Answer;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{self, BufRead};
 
 struct Signal<'a> {
     from: &'a str,
@@ -12,7 +15,8 @@ struct Signal<'a> {
 
 type Queue<'a> = VecDeque<Signal<'a>>;
 
-enum Module {
+/// Communication module together with its state.
+pub enum Module {
     FlipFlop {
         on: RefCell<bool>,
         out: Vec<String>,
@@ -27,18 +31,20 @@ enum Module {
 }
 
 impl Module {
-    fn parse(s: &str, parent: &mut HashMap<String, Vec<String>>) -> (String, Module) {
-        let mut parts = s.split(" -> ");
-        let lhs = parts.next().unwrap();
-        let rhs: Vec<_> = parts.next().unwrap().split(", ").collect();
+    fn parse(s: &str, parent: &mut HashMap<String, Vec<String>>) -> Result<(String, Module)> {
+        let (lhs, rhs) = input::split_once(s, s, " -> ")?;
+        let rhs: Vec<_> = rhs.split(", ").collect();
         let on = RefCell::new(false);
         let par = RefCell::new(HashMap::new());
         let out: Vec<_> = rhs.iter().map(|&s| String::from(s)).collect();
-        let (name, module) = match lhs.as_bytes()[0] {
-            b'%' => (String::from(&lhs[1..]), Module::FlipFlop { on, out }),
-            b'&' => (String::from(&lhs[1..]), Module::Conjunction { par, out }),
-            b'b' => (String::from(lhs), Module::Broadcaster { out }),
-            _ => panic!("Invalid input: '{}'", s),
+        let (name, module) = if let Some(name) = lhs.strip_prefix('%') {
+            (String::from(name), Module::FlipFlop { on, out })
+        } else if let Some(name) = lhs.strip_prefix('&') {
+            (String::from(name), Module::Conjunction { par, out })
+        } else if lhs == "broadcaster" {
+            (String::from(lhs), Module::Broadcaster { out })
+        } else {
+            return Err(Error::at(s, lhs, format!("Invalid module: '{lhs}'")));
         };
         for dest in rhs {
             parent
@@ -46,7 +52,7 @@ impl Module {
                 .or_default()
                 .push(name.clone());
         }
-        (name, module)
+        Ok((name, module))
     }
 
     fn send<'a>(&'a self, signal: Signal<'a>, queue: &mut Queue<'a>) {
@@ -71,7 +77,7 @@ impl Module {
                     queue.push_back(Signal {
                         from: signal.to,
                         to: dest,
-                        pulse: pulse,
+                        pulse,
                     });
                 }
             }
@@ -88,31 +94,28 @@ impl Module {
     }
 }
 
-struct Config(HashMap<String, Module>);
+/// Module configuration by name.
+pub struct Config(HashMap<String, Module>);
 
 impl Config {
-    fn read(path: &str) -> Config {
+    /// Parses the module configuration.
+    pub fn parse(text: &str) -> Result<Config> {
         let mut parent: HashMap<String, Vec<String>> = HashMap::new();
-        let config = HashMap::from_iter(
-            io::BufReader::new(File::open(path).unwrap())
-                .lines()
-                .filter_map(Result::ok)
-                .map(|line| Module::parse(&line, &mut parent)),
-        );
+        let config = HashMap::from_iter(input::parse_lines(text, |line| {
+            Module::parse(line, &mut parent)
+        })?);
         for (module_name, module) in config.iter() {
-            match module {
-                Module::Conjunction { par, .. } => {
-                    for parent_name in &parent[module_name] {
-                        par.borrow_mut().insert(parent_name.clone(), false);
-                    }
+            if let Module::Conjunction { par, .. } = module {
+                for parent_name in parent.get(module_name).into_iter().flatten() {
+                    par.borrow_mut().insert(parent_name.clone(), false);
                 }
-                _ => (),
             }
         }
-        Config(config)
+        Ok(Config(config))
     }
 
-    fn reset(&self) {
+    /// Resets all modules to their initial state.
+    pub fn reset(&self) {
         for module in self.0.values() {
             match module {
                 Module::FlipFlop { on, .. } => {
@@ -128,7 +131,8 @@ impl Config {
         }
     }
 
-    fn run(&self) -> (u64, u64) {
+    /// Pushes the button once; returns the number of low and high pulses sent.
+    pub fn run(&self) -> (u64, u64) {
         let mut queue = Queue::new();
         queue.push_back(Signal {
             from: "button",
@@ -149,15 +153,14 @@ impl Config {
         (low, high)
     }
 
-    fn find_parent_conjunction(&self, target: &str) -> &Module {
+    /// Conjunction module sending pulses to `target`.
+    pub fn find_parent_conjunction(&self, target: &str) -> Option<&Module> {
         self.0
             .iter()
-            .find(|(_, module)| match module {
-                Module::Conjunction { out, .. } if out.iter().any(|name| name == target) => true,
-                _ => false,
+            .find(|(_, module)| {
+                matches!(module, Module::Conjunction { out, .. } if out.iter().any(|name| name == target))
             })
             .map(|(_, module)| module)
-            .unwrap_or_else(|| panic!("Conjunction module not found"))
     }
 
     fn run_until_trigger(&self, trigger_name: &str) -> bool {
@@ -179,7 +182,8 @@ impl Config {
         found
     }
 
-    fn get_trigger_step(&self, trigger_name: &str) -> u64 {
+    /// Number of button pushes until `trigger_name` receives a low pulse.
+    pub fn get_trigger_step(&self, trigger_name: &str) -> u64 {
         let mut step = 1;
         while !self.run_until_trigger(trigger_name) {
             step += 1;
@@ -188,36 +192,32 @@ impl Config {
     }
 }
 
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while b != 0 {
-        let t = b;
-        b = a % b;
-        a = t;
-    }
-    a
-}
-
-pub fn part1(path: &str) {
-    let config = Config::read(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let config = Config::parse(text)?;
+    bench::parsed();
     let sum = (0..1000)
         .map(|_| config.run())
         .fold((0u64, 0u64), |(acc1, acc2), (x, y)| (acc1 + x, acc2 + y));
-    println!("{}", sum.0 * sum.1);
+    Ok((sum.0 * sum.1).into())
 }
 
-pub fn part2(path: &str) {
-    let config = Config::read(path);
-    let rx_conjunction = config.find_parent_conjunction("rx");
+pub fn part2(text: &str) -> Result<Answer> {
+    let config = Config::parse(text)?;
+    bench::parsed();
+    let rx_conjunction = config
+        .find_parent_conjunction("rx")
+        .ok_or_else(|| Error::new("No conjunction module sends pulses to 'rx'"))?;
     let rx_conjunction_triggers: Vec<_> = if let Module::Conjunction { par, .. } = rx_conjunction {
         par.borrow().keys().map(|s| s.to_string()).collect()
     } else {
-        panic!("Invalid module type");
+        unreachable!();
     };
     let mut mul = 1;
     for trigger_name in rx_conjunction_triggers.iter() {
         let step = config.get_trigger_step(trigger_name);
         config.reset();
-        mul = mul * step / gcd(mul, step);
+        mul = math::lcm(mul, step as i128)
+            .ok_or_else(|| Error::new("Number of button presses overflows"))?;
     }
-    println!("{mul}");
+    Ok(mul.into())
 }
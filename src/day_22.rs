@@ -1,29 +1,35 @@
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::search;
+use crate::Answer;
 use std::collections::HashSet;
-use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-type XY = [usize; 2];
-type XYZ = [usize; 3];
-type Brick = [XYZ; 2];
+pub type Xy = [usize; 2];
+pub type Xyz = [usize; 3];
+/// Both ends of a brick.
+pub type Brick = [Xyz; 2];
+/// Indices of the bricks adjacent to each brick.
+pub type Adjacency = Vec<HashSet<usize>>;
 
-fn parse_xyz(s: &str) -> XYZ {
-    s.split(',')
-        .map(|x| x.parse().unwrap())
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap()
+fn parse_xyz(line: &str, s: &str) -> Result<Xyz> {
+    let xyz: Vec<usize> = input::ints(line, s)?;
+    let n = xyz.len();
+    xyz.try_into()
+        .map_err(|_| Error::at(line, s, format!("Expected 3 coordinates, found {n}")))
 }
 
-fn parse_brick(s: &str) -> Brick {
-    let mut parts = s.split('~');
-    [
-        parse_xyz(parts.next().unwrap()),
-        parse_xyz(parts.next().unwrap()),
-    ]
+/// Parses a brick such as `1,0,1~1,2,1`.
+pub fn parse_brick(s: &str) -> Result<Brick> {
+    let (a, b) = input::split_once(s, s, "~")?;
+    let brick = [parse_xyz(s, a)?, parse_xyz(s, b)?];
+    if (0..3).any(|k| brick[0][k] > brick[1][k]) {
+        return Err(Error::at(s, b, "Brick must end after it starts"));
+    }
+    Ok(brick)
 }
 
-fn get_brick_xy(b: &Brick) -> Vec<XY> {
+fn get_brick_xy(b: &Brick) -> Vec<Xy> {
     if b[0][0] < b[1][0] {
         (b[0][0]..=b[1][0]).map(|x| [x, b[0][1]]).collect()
     } else if b[0][1] < b[1][1] {
@@ -33,12 +39,10 @@ fn get_brick_xy(b: &Brick) -> Vec<XY> {
     }
 }
 
-fn get_brick_graph(path: &str) -> (Vec<HashSet<usize>>, Vec<HashSet<usize>>) {
-    let mut bricks: Vec<Brick> = io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok)
-        .map(|l| parse_brick(&l))
-        .collect();
+/// Settles the bricks of the snapshot; returns for each brick (sorted by height)
+/// the bricks resting directly on it and the bricks it rests on.
+pub fn get_brick_graph(text: &str) -> Result<(Adjacency, Adjacency)> {
+    let mut bricks = input::parse_lines(text, parse_brick)?;
     bricks.sort_by_key(|b| b[0][2]);
     let mut above = vec![HashSet::new(); bricks.len()];
     let mut below = vec![HashSet::new(); bricks.len()];
@@ -60,33 +64,38 @@ fn get_brick_graph(path: &str) -> (Vec<HashSet<usize>>, Vec<HashSet<usize>>) {
             index[y][x] = i;
         }
     }
-    (above, below)
+    Ok((above, below))
 }
 
-pub fn part1(path: &str) {
-    let (above, below) = get_brick_graph(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let (above, below) = get_brick_graph(text)?;
+    bench::parsed();
     let count = above
         .iter()
         .filter(|&a| a.iter().all(|&j| below[j].len() > 1))
         .count();
-    println!("{count}");
+    Ok(count.into())
 }
 
-pub fn part2(path: &str) {
-    let (above, below) = get_brick_graph(path);
-    let sum: usize =
-        (0..above.len())
-            .map(|i| {
-                let mut falls = HashSet::new();
-                let mut queue = VecDeque::from(vec![i]);
-                falls.insert(i);
-                while let Some(i) = queue.pop_front() {
-                    queue.extend(above[i].iter().filter(|&&j| {
-                        below[j].iter().all(|k| falls.contains(k)) && falls.insert(j)
-                    }));
-                }
-                falls.len() - 1
-            })
-            .sum();
-    println!("{sum}");
+pub fn part2(text: &str) -> Result<Answer> {
+    let (above, below) = get_brick_graph(text)?;
+    bench::parsed();
+    let sum: usize = (0..above.len())
+        .map(|i| {
+            // A brick falls once every brick it rests on has fallen.
+            let mut supports: Vec<_> = below.iter().map(HashSet::len).collect();
+            let falls = search::bfs([i], |&j| {
+                above[j]
+                    .iter()
+                    .filter(|&&k| {
+                        supports[k] -= 1;
+                        supports[k] == 0
+                    })
+                    .copied()
+                    .collect::<Vec<_>>()
+            });
+            falls.len() - 1
+        })
+        .sum();
+    Ok(sum.into())
 }
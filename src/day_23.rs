@@ -1,72 +1,100 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::geometry::Direction;
+use crate::grid::{Grid, Pos};
+use crate::render::{self, Scene};
+use crate::Answer;
+use std::collections::HashSet;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord(usize, usize);
-
-struct Map {
-    map: Vec<Vec<u8>>,
-    m: usize,
-    n: usize,
+/// Hiking trails map.
+pub struct Map {
+    pub map: Grid<u8>,
 }
 
-struct Graph {
-    adj: Vec<Vec<(usize, usize)>>,
-    n: usize,
+/// Weighted graph of trail junctions; the first node is the start and the last node is the end.
+pub struct Graph {
+    pub adj: Vec<Vec<(usize, usize)>>,
+    pub n: usize,
+    /// Position of each junction.
+    pub nodes: Vec<Pos>,
 }
 
 impl Map {
-    fn read(path: &str) -> Map {
-        let map: Vec<Vec<u8>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| line.bytes().collect())
-            .collect();
-        let (m, n) = (map.len(), map[0].len());
-        Map { map, m, n }
+    /// Parses the hiking trails map.
+    pub fn parse(text: &str) -> Result<Map> {
+        let map = Grid::parse(text, b".#^v<>")?;
+        let (m, n) = (map.height(), map.width());
+        if m < 2 || n < 3 {
+            return Err(Error::new("Map is too small"));
+        }
+        if map[(0, 1)] != b'.' {
+            return Err(Error::at_cell(0, 1, "Expected start path"));
+        }
+        if map[(m - 1, n - 2)] != b'.' {
+            return Err(Error::at_cell(m - 1, n - 2, "Expected end path"));
+        }
+        Ok(Map { map })
     }
 
-    fn edges(&self, c: Coord, downhill: bool) -> Vec<Coord> {
-        let mut out = Vec::with_capacity(4);
-        match self.map[c.0][c.1] {
-            b'^' if downhill => out.push(Coord(c.0 - 1, c.1)),
-            b'v' if downhill => out.push(Coord(c.0 + 1, c.1)),
-            b'<' if downhill => out.push(Coord(c.0, c.1 - 1)),
-            b'>' if downhill => out.push(Coord(c.0, c.1 + 1)),
-            _ => {
-                if c.0 > 0 && self.map[c.0 - 1][c.1] != b'#' {
-                    out.push(Coord(c.0 - 1, c.1));
-                }
-                if c.0 < self.m - 1 && self.map[c.0 + 1][c.1] != b'#' {
-                    out.push(Coord(c.0 + 1, c.1));
-                }
-                if c.1 > 0 && self.map[c.0][c.1 - 1] != b'#' {
-                    out.push(Coord(c.0, c.1 - 1));
-                }
-                if c.1 < self.n - 1 && self.map[c.0][c.1 + 1] != b'#' {
-                    out.push(Coord(c.0, c.1 + 1));
+    fn edges(&self, c: Pos, downhill: bool) -> Vec<Pos> {
+        let slope = match self.map[c] {
+            b'^' => Some(Direction::Up),
+            b'v' => Some(Direction::Down),
+            b'<' => Some(Direction::Left),
+            b'>' => Some(Direction::Right),
+            _ => None,
+        };
+        match slope {
+            Some(d) if downhill => self.map.step(c, d).into_iter().collect(),
+            _ => self
+                .map
+                .neighbors4(c)
+                .filter(|&p| self.map[p] != b'#')
+                .collect(),
+        }
+    }
+
+    /// Cells of a trail of length `len` from junction `a` to junction `b`, excluding `a`.
+    fn trail(
+        &self,
+        a: Pos,
+        b: Pos,
+        len: usize,
+        downhill: bool,
+        junctions: &HashSet<Pos>,
+    ) -> Vec<Pos> {
+        for c in self.edges(a, downhill) {
+            let (mut c0, mut c1) = (a, c);
+            let mut out = vec![c1];
+            while !junctions.contains(&c1) {
+                match self.edges(c1, downhill).into_iter().find(|&c2| c2 != c0) {
+                    Some(c2) => (c0, c1) = (c1, c2),
+                    None => break,
                 }
+                out.push(c1);
+            }
+            if c1 == b && out.len() == len {
+                return out;
             }
         }
-        out
+        vec![]
     }
 
-    fn graph(&self, downhill: bool) -> Graph {
-        let mn = self.m * self.n;
-        let mut index = vec![vec![mn; self.n]; self.m];
-        let mut nodes = Vec::<Coord>::with_capacity(mn);
-        nodes.push(Coord(0, 1));
-        for i in 0..self.m {
-            for j in 0..self.n {
-                let c = Coord(i, j);
-                if self.map[i][j] != b'#' && self.edges(c, downhill).len() > 2 {
-                    index[i][j] = nodes.len();
-                    nodes.push(c);
-                }
+    /// Compresses the trails into a graph of junctions; slopes are one-way if `downhill` is set.
+    pub fn graph(&self, downhill: bool) -> Graph {
+        let (m, n) = (self.map.height(), self.map.width());
+        let mn = m * n;
+        let mut index = Grid::new(m, n, mn);
+        let mut nodes = Vec::<Pos>::with_capacity(mn);
+        nodes.push((0, 1));
+        for c in self.map.positions() {
+            if self.map[c] != b'#' && self.edges(c, downhill).len() > 2 {
+                index[c] = nodes.len();
+                nodes.push(c);
             }
         }
-        index[self.m - 1][self.n - 2] = nodes.len();
-        nodes.push(Coord(self.m - 1, self.n - 2));
+        index[(m - 1, n - 2)] = nodes.len();
+        nodes.push((m - 1, n - 2));
         let n = nodes.len();
         let mut adj = vec![Vec::with_capacity(4); n];
         for (i, c0) in nodes.iter().enumerate() {
@@ -74,13 +102,8 @@ impl Map {
                 let mut c0 = *c0;
                 let mut c1 = *c1;
                 let mut w = 1;
-                while index[c1.0][c1.1] == mn {
-                    if let Some(&c2) = self
-                        .edges(c1, downhill)
-                        .iter()
-                        .filter(|&&c2| c2 != c0)
-                        .next()
-                    {
+                while index[c1] == mn {
+                    if let Some(&c2) = self.edges(c1, downhill).iter().find(|&&c2| c2 != c0) {
                         c0 = c1;
                         c1 = c2;
                         w += 1;
@@ -88,24 +111,35 @@ impl Map {
                         continue 'outer;
                     }
                 }
-                let j = index[c1.0][c1.1];
+                let j = index[c1];
                 adj[i].push((j, w));
             }
         }
-        Graph { adj, n }
+        Graph { adj, n, nodes }
     }
 }
 
 impl Graph {
-    fn search(&self) -> usize {
+    /// Length of the longest path from the start to the end.
+    pub fn search(&self) -> usize {
+        self.longest_path().0
+    }
+
+    /// Length of the longest path from the start to the end, and the junctions along it
+    /// together with the distance at which they are reached.
+    pub fn longest_path(&self) -> (usize, Vec<(usize, usize)>) {
         let mut max_len = 0;
+        let mut best = vec![];
         let mut visited = vec![false; self.n];
         let mut edge = vec![0usize; self.n];
         let mut path = Vec::with_capacity(self.n);
         path.push((0, 0));
         'outer: while let Some(&(i0, w0)) = path.last() {
             if i0 == self.n - 1 {
-                max_len = max_len.max(w0);
+                if w0 > max_len {
+                    max_len = w0;
+                    best = path.clone();
+                }
             } else {
                 visited[i0] = true;
                 while edge[i0] < self.adj[i0].len() {
@@ -121,14 +155,33 @@ impl Graph {
             visited[i0] = false;
             path.pop();
         }
-        max_len
+        (max_len, best)
     }
 }
 
-pub fn part1(path: &str) {
-    println!("{}", Map::read(path).graph(true).search());
+pub fn part1(text: &str) -> Result<Answer> {
+    let map = Map::parse(text)?;
+    bench::parsed();
+    Ok(map.graph(true).search().into())
+}
+
+pub fn part2(text: &str) -> Result<Answer> {
+    let map = Map::parse(text)?;
+    bench::parsed();
+    Ok(map.graph(false).search().into())
 }
 
-pub fn part2(path: &str) {
-    println!("{}", Map::read(path).graph(false).search());
+/// Map with the longest hike of part 1 highlighted.
+pub fn render(text: &str) -> Result<Scene> {
+    let map = Map::parse(text)?;
+    let graph = map.graph(true);
+    let (_, path) = graph.longest_path();
+    let junctions: HashSet<_> = graph.nodes.iter().copied().collect();
+    let mut cells = vec![graph.nodes[0]];
+    for w in path.windows(2) {
+        let ((i, w0), (j, w1)) = (w[0], w[1]);
+        let (a, b) = (graph.nodes[i], graph.nodes[j]);
+        cells.extend(map.trail(a, b, w1 - w0, true, &junctions));
+    }
+    Ok(Scene::new(map.map).layer(render::RED, cells))
 }
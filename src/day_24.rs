@@ -1,116 +1,113 @@
-use ndarray::{array, Array1, Array2};
-use ndarray_linalg::Solve;
-use std::fs::File;
-use std::io::{self, BufRead};
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::math::{self, Rational};
+use crate::Answer;
 
-type Coord = [f64; 3];
+pub type Coord = [i64; 3];
 
-struct Hailstone {
-    p: Coord,
-    v: Coord,
+/// Hailstone position and velocity.
+pub struct Hailstone {
+    pub p: Coord,
+    pub v: Coord,
 }
 
-fn parse_coord(s: &str) -> Coord {
-    s.split(",")
-        .map(|x| x.trim().parse().unwrap())
-        .collect::<Vec<_>>()
+fn parse_coord(line: &str, s: &str) -> Result<Coord> {
+    let coord: Vec<i64> = input::ints(line, s)?;
+    let n = coord.len();
+    coord
         .try_into()
-        .unwrap()
+        .map_err(|_| Error::at(line, s, format!("Expected 3 coordinates, found {n}")))
 }
 
 impl Hailstone {
-    fn parse(s: &str) -> Hailstone {
-        let mut pv = s.split(" @ ");
-        Hailstone {
-            p: parse_coord(pv.next().unwrap()),
-            v: parse_coord(pv.next().unwrap()),
-        }
+    /// Parses a hailstone such as `19, 13, 30 @ -2,  1, -2`.
+    pub fn parse(s: &str) -> Result<Hailstone> {
+        let (p, v) = input::split_once(s, s, " @ ")?;
+        Ok(Hailstone {
+            p: parse_coord(s, p)?,
+            v: parse_coord(s, v)?,
+        })
     }
 }
 
-fn read_hailstones(path: &str) -> Vec<Hailstone> {
-    io::BufReader::new(File::open(path).unwrap())
-        .lines()
-        .filter_map(Result::ok)
-        .map(|line| Hailstone::parse(&line))
-        .collect()
+/// Parses all hailstones.
+pub fn read_hailstones(text: &str) -> Result<Vec<Hailstone>> {
+    input::parse_lines(text, Hailstone::parse)
 }
 
-fn intersect_xy(h1: &Hailstone, h2: &Hailstone, min: f64, max: f64) -> bool {
-    let a: Array2<f64> = array![[h1.v[0], -h2.v[0]], [h1.v[1], -h2.v[1]]];
-    let b: Array1<f64> = array![h2.p[0] - h1.p[0], h2.p[1] - h1.p[1]];
-    if let Ok(t) = a.solve_into(b) {
-        let (x, y) = (h1.p[0] + h1.v[0] * t[0], h1.p[1] + h1.v[1] * t[0]);
-        t[0] > 0.0 && t[1] > 0.0 && min <= x && x <= max && min <= y && y <= max
-    } else {
-        false
-    }
+fn q(x: i64) -> Rational {
+    Rational::from(x)
 }
 
-fn solve_rock(h: &Vec<Hailstone>) {
-    let mut r = Hailstone {
-        p: [0.0; 3],
-        v: [0.0; 3],
+/// Whether the future paths of both hailstones cross (ignoring the z axis) inside
+/// the `[min, max]` test area.
+pub fn intersect_xy(h1: &Hailstone, h2: &Hailstone, min: i64, max: i64) -> bool {
+    let a = [[q(h1.v[0]), -q(h2.v[0])], [q(h1.v[1]), -q(h2.v[1])]];
+    let b = [q(h2.p[0] - h1.p[0]), q(h2.p[1] - h1.p[1])];
+    let Some([t1, t2]) = math::solve(a, b) else {
+        return false;
     };
-    let max_vel = 1000i32;
+    let (x, y) = (q(h1.p[0]) + q(h1.v[0]) * t1, q(h1.p[1]) + q(h1.v[1]) * t1);
+    let area = q(min)..=q(max);
+    t1 > Rational::ZERO && t2 > Rational::ZERO && area.contains(&x) && area.contains(&y)
+}
+
+/// Integer position on the axes `a` and `b` at which a rock thrown with
+/// velocity `(wa, wb)` on those axes hits the first three hailstones, if there is one.
+/// Solves for the rock position and the first two collision times, then checks that
+/// the third hailstone is hit too.
+fn throw(h: &[Hailstone], a: usize, b: usize, wa: i64, wb: i64) -> Option<(i64, i64)> {
+    let m = [
+        [1, 0, wa - h[0].v[a], 0, 0],
+        [0, 1, wb - h[0].v[b], 0, 0],
+        [1, 0, 0, wa - h[1].v[a], 0],
+        [0, 1, 0, wb - h[1].v[b], 0],
+        [1, 0, 0, 0, wa - h[2].v[a]],
+    ];
+    let rhs = [h[0].p[a], h[0].p[b], h[1].p[a], h[1].p[b], h[2].p[a]].map(q);
+    let s = math::solve(m.map(|row| row.map(q)), rhs)?;
+    let (pa, pb, t2) = (s[0].to_integer()?, s[1].to_integer()?, s[4].to_integer()?);
+    (pb + (wb as i128 - h[2].v[b] as i128) * t2 == h[2].p[b] as i128)
+        .then_some((pa as i64, pb as i64))
+}
+
+/// Sum of the initial position coordinates of a rock hitting all hailstones.
+pub fn solve_rock(h: &[Hailstone]) -> Option<i64> {
+    let max_vel = 1000i64;
     let mut w0w1s: Vec<_> = (-max_vel..=max_vel)
         .flat_map(|v0| (-max_vel..=max_vel).map(move |v1| (v0, v1)))
         .collect();
-    w0w1s.sort_by_key(|&(w0, w1)| (w0 * w0 + w1 * w1, w0, w1));
-    for &(w0, w1) in w0w1s.iter() {
-        r.v[0] = w0 as f64;
-        r.v[1] = w1 as f64;
-        let a: Array2<f64> = array![
-            [1.0, 0.0, r.v[0] - h[0].v[0], 0.0, 0.0],
-            [0.0, 1.0, r.v[1] - h[0].v[1], 0.0, 0.0],
-            [1.0, 0.0, 0.0, r.v[0] - h[1].v[0], 0.0],
-            [0.0, 1.0, 0.0, r.v[1] - h[1].v[1], 0.0],
-            [1.0, 0.0, 0.0, 0.0, r.v[0] - h[2].v[0]],
-        ];
-        let b: Array1<f64> = array![h[0].p[0], h[0].p[1], h[1].p[0], h[1].p[1], h[2].p[0]];
-        if let Ok(s) = a.solve_into(b) {
-            if (s[1].round() + (r.v[1] - h[2].v[1]) * s[4].round() - h[2].p[1]).abs() < 1e-6 {
-                r.p[0] = s[0].round();
-                r.p[1] = s[1].round();
-                break;
-            }
-        }
-    }
+    w0w1s.sort_unstable_by_key(|&(w0, w1)| (w0 * w0 + w1 * w1, w0, w1));
+    let (w0, (p0, p1)) = w0w1s
+        .iter()
+        .find_map(|&(w0, w1)| Some((w0, throw(h, 0, 1, w0, w1)?)))?;
     let mut w2s: Vec<_> = (-max_vel..=max_vel).collect();
-    w2s.sort_by_key(|&w2| w2.abs());
-    for &w2 in w2s.iter() {
-        r.v[2] = w2 as f64;
-        let a: Array2<f64> = array![
-            [1.0, 0.0, r.v[0] - h[0].v[0], 0.0, 0.0],
-            [0.0, 1.0, r.v[2] - h[0].v[2], 0.0, 0.0],
-            [1.0, 0.0, 0.0, r.v[0] - h[1].v[0], 0.0],
-            [0.0, 1.0, 0.0, r.v[2] - h[1].v[2], 0.0],
-            [1.0, 0.0, 0.0, 0.0, r.v[0] - h[2].v[0]],
-        ];
-        let b: Array1<f64> = array![h[0].p[0], h[0].p[2], h[1].p[0], h[1].p[2], h[2].p[0]];
-        if let Ok(s) = a.solve_into(b) {
-            if (s[1].round() + (r.v[2] - h[2].v[2]) * s[4].round() - h[2].p[2]).abs() < 1e-6 {
-                r.p[2] = s[1].round();
-                break;
-            }
-        }
-    }
-    println!("{}", r.p[0] + r.p[1] + r.p[2]);
+    w2s.sort_unstable_by_key(|&w2| (w2.abs(), w2));
+    let (_, p2) = w2s.iter().find_map(|&w2| throw(h, 0, 2, w0, w2))?;
+    Some(p0 + p1 + p2)
 }
 
-pub fn part1(path: &str) {
-    let h = read_hailstones(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let h = read_hailstones(text)?;
+    bench::parsed();
     let n = h.len();
-    let count: usize = (0..n - 1)
+    let count: usize = (0..n.saturating_sub(1))
         .map(|i| {
             (i + 1..n)
-                .filter(|&j| intersect_xy(&h[i], &h[j], 2e14, 4e14))
+                .filter(|&j| intersect_xy(&h[i], &h[j], 200_000_000_000_000, 400_000_000_000_000))
                 .count()
         })
         .sum();
-    println!("{count}");
+    Ok(count.into())
 }
 
-pub fn part2(path: &str) {
-    solve_rock(&read_hailstones(path));
+pub fn part2(text: &str) -> Result<Answer> {
+    let h = read_hailstones(text)?;
+    bench::parsed();
+    if h.len() < 3 {
+        return Err(Error::new("Expected at least 3 hailstones"));
+    }
+    let sum = solve_rock(&h).ok_or_else(|| Error::new("No rock hits every hailstone"))?;
+    Ok(sum.into())
 }
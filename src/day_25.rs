@@ -1,30 +1,24 @@
+use crate::bench;
+use crate::error::{Error, Result};
+use crate::input;
+use crate::Answer;
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{self, BufRead};
 
-struct Graph {
-    mat: Vec<Vec<i32>>,
-    n: usize,
+/// Undirected graph of components as an adjacency matrix.
+pub struct Graph {
+    pub mat: Vec<Vec<i32>>,
+    pub n: usize,
 }
 
 impl Graph {
-    fn read(path: &str) -> Graph {
-        let conns: Vec<Vec<String>> = io::BufReader::new(File::open(path).unwrap())
-            .lines()
-            .filter_map(Result::ok)
-            .map(|line| {
-                line.split(' ')
-                    .enumerate()
-                    .map(|(i, s)| {
-                        if i == 0 {
-                            String::from(&s[0..s.len() - 1])
-                        } else {
-                            String::from(s)
-                        }
-                    })
-                    .collect()
-            })
-            .collect();
+    /// Parses the wiring diagram.
+    pub fn parse(text: &str) -> Result<Graph> {
+        let conns: Vec<Vec<String>> = input::parse_lines(text, |line| {
+            let (name, others) = input::split_once(line, line, ": ")?;
+            let mut conn = vec![String::from(name)];
+            conn.extend(others.split_ascii_whitespace().map(String::from));
+            Ok(conn)
+        })?;
         let mut nodes: Vec<&String> =
             Vec::from_iter(HashSet::<&String>::from_iter(conns.iter().flatten()));
         nodes.sort();
@@ -40,15 +34,20 @@ impl Graph {
                 mat[j][i] += 1;
             }
         }
-        Graph { mat, n }
+        if n < 2 {
+            return Err(Error::new("Expected at least 2 components"));
+        }
+        Ok(Graph { mat, n })
     }
 
-    fn stoer_wagner(&self, expected_cut: i32) -> (i32, Vec<usize>) {
+    /// Stoer-Wagner minimum cut. Stops early once a cut of weight `expected_cut` is
+    /// found; returns the cut weight and the nodes on one side of it.
+    pub fn stoer_wagner(&self, expected_cut: i32) -> (i32, Vec<usize>) {
         let mut out = (i32::MAX, vec![]);
         let mut mat = self.mat.clone();
         let mut grp = vec![Vec::with_capacity(self.n); self.n];
-        for i in 0..self.n {
-            grp[i].push(i);
+        for (i, g) in grp.iter_mut().enumerate() {
+            g.push(i);
         }
         for phase in 1..self.n {
             let mut w = mat[0].clone(); // Keeps w(A, i) for all vertices i.
@@ -66,6 +65,7 @@ impl Graph {
                     .map(|(i, _)| i)
                     .unwrap();
                 // Update w(A, i) for all vertices i (as t is being added to A).
+                #[allow(clippy::needless_range_loop)]
                 for i in 0..self.n {
                     w[i] += mat[t][i];
                 }
@@ -77,9 +77,11 @@ impl Graph {
             // Merge s and t.
             let mut grp_t = grp[t].clone();
             grp[s].append(&mut grp_t);
+            #[allow(clippy::needless_range_loop)]
             for i in 0..self.n {
                 mat[s][i] += mat[t][i];
             }
+            #[allow(clippy::needless_range_loop)]
             for i in 0..self.n {
                 mat[i][s] = mat[s][i];
             }
@@ -93,8 +95,9 @@ impl Graph {
     }
 }
 
-pub fn part1(path: &str) {
-    let graph = Graph::read(path);
+pub fn part1(text: &str) -> Result<Answer> {
+    let graph = Graph::parse(text)?;
+    bench::parsed();
     let (_, cut) = graph.stoer_wagner(3);
-    println!("{}", cut.len() * (graph.n - cut.len()));
+    Ok((cut.len() * (graph.n - cut.len())).into())
 }
@@ -0,0 +1,116 @@
+use std::fmt;
+
+/// Error produced while reading or parsing a puzzle input.
+///
+/// Line and column numbers start at 1; zero means the position is unknown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Error without a known position.
+    pub fn new(message: impl Into<String>) -> Error {
+        Error {
+            file: String::new(),
+            line: 0,
+            column: 0,
+            message: message.into(),
+        }
+    }
+
+    /// Error pointing at `token`, which must be a subslice of `line`.
+    pub fn at(line: &str, token: &str, message: impl Into<String>) -> Error {
+        Error {
+            column: column(line, token),
+            ..Error::new(message)
+        }
+    }
+
+    /// Error pointing at the given (0-based) row and column of a character map.
+    pub fn at_cell(i: usize, j: usize, message: impl Into<String>) -> Error {
+        Error {
+            line: i + 1,
+            column: j + 1,
+            ..Error::new(message)
+        }
+    }
+}
+
+/// Column (starting at 1) of `token` inside `line`, or 0 if it is not a subslice.
+fn column(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    if pos >= start && pos <= start + line.len() {
+        pos - start + 1
+    } else {
+        0
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.file.is_empty() {
+            write!(f, "{}:", self.file)?;
+        }
+        if self.line > 0 {
+            write!(f, "{}:", self.line)?;
+            if self.column > 0 {
+                write!(f, "{}:", self.column)?;
+            }
+        }
+        if !self.file.is_empty() || self.line > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Attaches the position of an error as it propagates up from the line parsers.
+pub trait Context<T> {
+    /// Sets the line number (starting at 1) unless it is already known.
+    fn line(self, line: usize) -> Result<T>;
+
+    /// Sets the file name unless it is already known.
+    fn file(self, path: &str) -> Result<T>;
+
+    /// Makes the column of an error found in `s` relative to `line`, of which `s`
+    /// must be a subslice.
+    fn within(self, line: &str, s: &str) -> Result<T>;
+}
+
+impl<T> Context<T> for Result<T> {
+    fn line(self, line: usize) -> Result<T> {
+        self.map_err(|mut err| {
+            if err.line == 0 {
+                err.line = line;
+            }
+            err
+        })
+    }
+
+    fn file(self, path: &str) -> Result<T> {
+        self.map_err(|mut err| {
+            if err.file.is_empty() {
+                err.file = String::from(path);
+            }
+            err
+        })
+    }
+
+    fn within(self, line: &str, s: &str) -> Result<T> {
+        self.map_err(|mut err| {
+            if err.column > 0 {
+                err.column += column(line, s).max(1) - 1;
+            }
+            err
+        })
+    }
+}
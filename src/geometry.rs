@@ -0,0 +1,139 @@
+use crate::grid::Pos;
+use std::ops::{Add, Mul, Sub};
+
+/// One of the four directions on a grid, with rows growing downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All directions.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Direction for one of the letters `U`, `D`, `L` and `R`.
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'U' => Some(Direction::Up),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// Direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    /// Direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().reverse()
+    }
+
+    /// Opposite direction.
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Change of position after a single step.
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(-1, 0),
+            Direction::Down => Point::new(1, 0),
+            Direction::Left => Point::new(0, -1),
+            Direction::Right => Point::new(0, 1),
+        }
+    }
+
+    /// Unsigned position one step from `p`, or `None` if it would be negative.
+    pub fn step(self, p: Pos) -> Option<Pos> {
+        let d = self.delta();
+        Some((
+            p.0.checked_add_signed(d.i as isize)?,
+            p.1.checked_add_signed(d.j as isize)?,
+        ))
+    }
+}
+
+/// Signed row and column, possibly outside of a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub i: i64,
+    pub j: i64,
+}
+
+impl Point {
+    pub fn new(i: i64, j: i64) -> Point {
+        Point { i, j }
+    }
+
+    /// Point one step away in direction `d`.
+    pub fn step(self, d: Direction) -> Point {
+        self + d.delta()
+    }
+
+    /// Point `n` steps away in direction `d`.
+    pub fn step_by(self, d: Direction, n: i64) -> Point {
+        self + d.delta() * n
+    }
+
+    /// Manhattan distance to `other`.
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.i - other.i).abs() + (self.j - other.j).abs()
+    }
+
+    /// Unsigned position, or `None` if either coordinate is negative.
+    pub fn pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.i).ok()?, usize::try_from(self.j).ok()?))
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((i, j): Pos) -> Point {
+        Point::new(i as i64, j as i64)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.i + other.i, self.j + other.j)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.i - other.i, self.j - other.j)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, k: i64) -> Point {
+        Point::new(self.i * k, self.j * k)
+    }
+}
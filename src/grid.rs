@@ -0,0 +1,242 @@
+use crate::error::{Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::input;
+use std::ops::{Index, IndexMut};
+
+/// Row and column of a cell.
+pub type Pos = (usize, usize);
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    /// Grid of the given size filled with `value`.
+    pub fn new(height: usize, width: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; height * width],
+            height,
+            width,
+        }
+    }
+
+    /// Grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Rows differ in length"
+        );
+        Grid {
+            cells: rows.into_iter().flatten().collect(),
+            height,
+            width,
+        }
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Cell at `p`, or `None` if it is outside of the grid.
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        self.contains(p).then(|| &self[p])
+    }
+
+    /// Whether `p` is inside of the grid.
+    pub fn contains(&self, p: Pos) -> bool {
+        p.0 < self.height && p.1 < self.width
+    }
+
+    /// Cell at the signed point `p`, or `None` if it is outside of the grid.
+    pub fn get_point(&self, p: Point) -> Option<&T> {
+        self.get(p.pos()?)
+    }
+
+    /// Position one step from `p` in direction `d`, or `None` if it leaves the grid.
+    pub fn step(&self, p: Pos, d: Direction) -> Option<Pos> {
+        d.step(p).filter(|&q| self.contains(q))
+    }
+
+    /// Cells in row `i`.
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    /// Cells in column `j`, from top to bottom.
+    pub fn col(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.height).map(move |i| &self[(i, j)])
+    }
+
+    /// All rows from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// All columns from left to right.
+    pub fn cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |j| self.col(j))
+    }
+
+    /// All cells together with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    /// Position of the first cell (row by row) equal to `value`.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        let k = self.cells.iter().position(|x| x == value)?;
+        Some((k / self.width, k % self.width))
+    }
+
+    /// Up, down, left and right neighbors of `p` inside of the grid.
+    pub fn neighbors4(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        const OFFSETS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        self.offset_all(p, &OFFSETS)
+    }
+
+    /// Neighbors of `p` inside of the grid, including the diagonal ones.
+    pub fn neighbors8(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+        self.offset_all(p, &OFFSETS)
+    }
+
+    fn offset_all<'a>(
+        &'a self,
+        p: Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets.iter().filter_map(move |&(di, dj)| {
+            let q = (p.0.checked_add_signed(di)?, p.1.checked_add_signed(dj)?);
+            self.contains(q).then_some(q)
+        })
+    }
+
+    /// Grid with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    /// Grid with rows and columns swapped.
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |i, j| (j, i))
+    }
+
+    /// Grid rotated by a quarter turn clockwise.
+    pub fn rotated_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.width, h, |i, j| (h - 1 - j, i))
+    }
+
+    /// Grid rotated by a quarter turn counterclockwise.
+    pub fn rotated_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(w, self.height, |i, j| (j, w - 1 - i))
+    }
+
+    /// Grid of the given size whose cell `(i, j)` is the cell `f(i, j)` of this grid.
+    fn remap<F: Fn(usize, usize) -> Pos>(&self, height: usize, width: usize, f: F) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .map(|(i, j)| self[f(i, j)].clone())
+            .collect();
+        Grid {
+            cells,
+            height,
+            width,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a rectangular character map, checking that it only contains `allowed` bytes.
+    pub fn parse(text: &str, allowed: &[u8]) -> Result<Grid<u8>> {
+        let rows = input::parse_lines(text, |line| {
+            match line.bytes().position(|b| !allowed.contains(&b)) {
+                Some(j) => Err(Error::at(
+                    line,
+                    &line[j..],
+                    format!("Invalid tile: '{}'", line[j..].chars().next().unwrap()),
+                )),
+                None => Ok(line.bytes().collect::<Vec<_>>()),
+            }
+        })?;
+        if rows.is_empty() || rows[0].is_empty() {
+            return Err(Error::new("Empty map"));
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != rows[0].len() {
+                return Err(Error::at_cell(
+                    i,
+                    row.len().min(rows[0].len()),
+                    format!("Expected {} tiles, found {}", rows[0].len(), row.len()),
+                ));
+            }
+        }
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): Pos) -> &T {
+        assert!(j < self.width, "Column {j} out of bounds");
+        &self.cells[i * self.width + j]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (i, j): Pos) -> &mut T {
+        assert!(j < self.width, "Column {j} out of bounds");
+        &mut self.cells[i * self.width + j]
+    }
+}
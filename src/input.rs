@@ -0,0 +1,149 @@
+use crate::error::{Context, Error, Result};
+use std::fs;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Reads the whole file.
+pub fn read_to_string(path: &str) -> Result<String> {
+    fs::read_to_string(path).map_err(|err| Error {
+        file: String::from(path),
+        ..Error::new(err.to_string())
+    })
+}
+
+/// Reads everything from `reader`, such as a locked stdin.
+pub fn read_all<R: BufRead>(mut reader: R) -> Result<String> {
+    let mut text = String::new();
+    reader
+        .read_to_string(&mut text)
+        .map_err(|err| Error::new(err.to_string()))?;
+    Ok(text)
+}
+
+/// Parses each line of the text, attaching the line number to any error.
+pub fn parse_lines<T, F>(text: &str, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| f(line).line(i + 1))
+        .collect()
+}
+
+/// Parses `token`, which must be a subslice of `line`.
+pub fn parse<T: FromStr>(line: &str, token: &str) -> Result<T> {
+    token
+        .parse()
+        .map_err(|_| Error::at(line, token, format!("Invalid number: '{token}'")))
+}
+
+/// Strips `prefix` from the start of `line`.
+pub fn expect_prefix<'a>(line: &'a str, prefix: &str) -> Result<&'a str> {
+    line.strip_prefix(prefix)
+        .ok_or_else(|| Error::at(line, line, format!("Expected '{prefix}'")))
+}
+
+/// Splits `s` (a subslice of `line`) once at `delim`.
+pub fn split_once<'a>(line: &str, s: &'a str, delim: &str) -> Result<(&'a str, &'a str)> {
+    s.split_once(delim)
+        .ok_or_else(|| Error::at(line, s, format!("Expected '{delim}'")))
+}
+
+/// Parses the whitespace-separated numbers in `s` (a subslice of `line`).
+pub fn numbers<T: FromStr>(line: &str, s: &str) -> Result<Vec<T>> {
+    s.split_ascii_whitespace().map(|x| parse(line, x)).collect()
+}
+
+/// Parses every (optionally negative) integer in `s` (a subslice of `line`), skipping
+/// any characters in between.
+pub fn ints<T: FromStr>(line: &str, s: &str) -> Result<Vec<T>> {
+    let bytes = s.as_bytes();
+    let mut out = vec![];
+    let mut k = 0;
+    while k < bytes.len() {
+        if !bytes[k].is_ascii_digit() {
+            k += 1;
+            continue;
+        }
+        let start = if k > 0 && bytes[k - 1] == b'-' {
+            k - 1
+        } else {
+            k
+        };
+        while k < bytes.len() && bytes[k].is_ascii_digit() {
+            k += 1;
+        }
+        out.push(parse(line, &s[start..k])?);
+    }
+    Ok(out)
+}
+
+/// Value of a header line such as `Time:   7  15  30`, with `key`, the colon and any
+/// whitespace after it stripped.
+pub fn header<'a>(line: &'a str, key: &str) -> Result<&'a str> {
+    line.strip_prefix(key)
+        .and_then(|s| s.strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or_else(|| Error::at(line, line, format!("Expected '{key}:'")))
+}
+
+/// Splits a labelled record such as `Card  1: 41 48 | 83 86` into its id and its body.
+pub fn record<'a, T: FromStr>(line: &'a str, label: &str) -> Result<(T, &'a str)> {
+    let rest = line
+        .strip_prefix(label)
+        .filter(|s| s.starts_with(' '))
+        .ok_or_else(|| Error::at(line, line, format!("Expected '{label} '")))?;
+    let (id, body) = split_once(line, rest.trim_start(), ":")?;
+    Ok((parse(line, id)?, body.trim_start()))
+}
+
+/// Group of consecutive non-blank lines.
+pub struct Block<'a> {
+    /// Number (starting at 1) of the first line of the block.
+    pub line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    /// First line of the block, such as a header, and the block of the remaining lines.
+    pub fn split_first(&self) -> (&'a str, Block<'a>) {
+        let rest = Block {
+            line: self.line + 1,
+            lines: self.lines[1..].to_vec(),
+        };
+        (self.lines[0], rest)
+    }
+
+    /// Parses each line of the block, attaching the line number to any error.
+    pub fn parse_lines<T, F>(&self, mut f: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&'a str) -> Result<T>,
+    {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(k, line)| f(line).line(self.line + k))
+            .collect()
+    }
+}
+
+/// Splits the text into blocks separated by blank lines.
+pub fn blocks(text: &str) -> Vec<Block<'_>> {
+    let mut out: Vec<Block> = vec![];
+    let mut in_block = false;
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            in_block = false;
+        } else if in_block {
+            out.last_mut().unwrap().lines.push(line);
+        } else {
+            out.push(Block {
+                line: i + 1,
+                lines: vec![line],
+            });
+            in_block = true;
+        }
+    }
+    out
+}
@@ -0,0 +1,214 @@
+/// Half-open interval `[start, end)` of integers; empty if `start >= end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    pub fn new(start: u64, end: u64) -> Interval {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Number of integers in the interval.
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// Integers in both intervals.
+    pub fn intersect(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Splits into the integers below `x` and those at or above it.
+    pub fn split_at(&self, x: u64) -> (Interval, Interval) {
+        let x = x.clamp(self.start, self.end.max(self.start));
+        (Interval::new(self.start, x), Interval::new(x, self.end))
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { intervals: vec![] }
+    }
+
+    /// Intervals of the set in increasing order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn count(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    /// Smallest integer in the set.
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|int| int.start)
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        let i = self.intervals.partition_point(|int| int.end <= x);
+        self.intervals.get(i).is_some_and(|int| int.contains(x))
+    }
+
+    /// Adds all integers of `int` to the set.
+    pub fn insert(&mut self, int: Interval) {
+        if int.is_empty() {
+            return;
+        }
+        // Intervals before `i` end before `int`, intervals from `j` start after it.
+        let i = self.intervals.partition_point(|x| x.end < int.start);
+        let j = self.intervals.partition_point(|x| x.start <= int.end);
+        let merged = self.intervals[i..j].iter().fold(int, |acc, x| {
+            Interval::new(acc.start.min(x.start), acc.end.max(x.end))
+        });
+        self.intervals.splice(i..j, [merged]);
+    }
+
+    /// Integers in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = self.clone();
+        for &int in other.intervals.iter() {
+            out.insert(int);
+        }
+        out
+    }
+
+    /// Integers in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            let int = a.intersect(&b);
+            if !int.is_empty() {
+                out.push(int);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: out }
+    }
+
+    /// Integers in this set but not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = vec![];
+        let mut j = 0;
+        for &a in self.intervals.iter() {
+            let mut start = a.start;
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < a.end {
+                let b = other.intervals[k];
+                if b.start > start {
+                    out.push(Interval::new(start, b.start));
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < a.end {
+                out.push(Interval::new(start, a.end));
+            }
+        }
+        IntervalSet { intervals: out }
+    }
+
+    /// Splits the set into the integers satisfying `pred` and the others. Within each
+    /// interval, `pred` must hold for a (possibly empty) prefix and fail for the rest,
+    /// like for `partition_point`.
+    pub fn split_by<F: FnMut(u64) -> bool>(&self, mut pred: F) -> (IntervalSet, IntervalSet) {
+        let mut yes = IntervalSet::new();
+        let mut no = IntervalSet::new();
+        for int in self.intervals.iter() {
+            // Binary search for the first integer not satisfying `pred`.
+            let (mut lo, mut hi) = (int.start, int.end);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if pred(mid) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            let (a, b) = int.split_at(lo);
+            yes.insert(a);
+            no.insert(b);
+        }
+        (yes, no)
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(int: Interval) -> IntervalSet {
+        let mut out = IntervalSet::new();
+        out.insert(int);
+        out
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> IntervalSet {
+        let mut out = IntervalSet::new();
+        for int in iter {
+            out.insert(int);
+        }
+        out
+    }
+}
+
+/// Box of integer points with one interval per dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalBox<const N: usize>(pub [Interval; N]);
+
+impl<const N: usize> IntervalBox<N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    /// Number of integer points in the box.
+    pub fn volume(&self) -> u128 {
+        self.0.iter().map(|int| int.len() as u128).product()
+    }
+
+    pub fn contains(&self, p: &[u64; N]) -> bool {
+        self.0.iter().zip(p).all(|(int, &x)| int.contains(x))
+    }
+
+    /// Points in both boxes.
+    pub fn intersect(&self, other: &IntervalBox<N>) -> IntervalBox<N> {
+        IntervalBox(std::array::from_fn(|k| self.0[k].intersect(&other.0[k])))
+    }
+
+    /// Splits along dimension `dim` into the points below `x` and those at or above it.
+    pub fn split_at(&self, dim: usize, x: u64) -> (IntervalBox<N>, IntervalBox<N>) {
+        let (lo, hi) = self.0[dim].split_at(x);
+        let (mut a, mut b) = (*self, *self);
+        a.0[dim] = lo;
+        b.0[dim] = hi;
+        (a, b)
+    }
+}
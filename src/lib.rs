@@ -0,0 +1,94 @@
+//! Solutions to [Advent of Code 2023](https://adventofcode.com/2023).
+//!
+//! Each `day_XX` module exposes the puzzle parsers and solvers, together with
+//! `part1` and `part2` functions taking the puzzle text. Use [`solve_file`] or
+//! [`solve_reader`] to run them on a file or any other source.
+
+pub mod answer;
+pub mod bench;
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod pool;
+pub mod render;
+pub mod report;
+pub mod search;
+pub mod store;
+pub mod verify;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+
+use error::Context;
+use std::io::BufRead;
+
+/// Solver of a single puzzle part, taking the puzzle text.
+pub type Part = fn(&str) -> Result<Answer>;
+
+/// Runs a part on the contents of a file, attaching the file name to any error.
+pub fn solve_file(solve: Part, path: &str) -> Result<Answer> {
+    let text = input::read_to_string(path)?;
+    solve(&text).file(path)
+}
+
+/// Runs a part on everything read from `reader`, such as a locked stdin.
+pub fn solve_reader<R: BufRead>(solve: Part, reader: R) -> Result<Answer> {
+    solve(&input::read_all(reader)?)
+}
+
+/// Solvers of both parts for each day. Day 25 only has a single part.
+pub const DAYS: [(Part, Option<Part>); 25] = [
+    (day_01::part1, Some(day_01::part2)),
+    (day_02::part1, Some(day_02::part2)),
+    (day_03::part1, Some(day_03::part2)),
+    (day_04::part1, Some(day_04::part2)),
+    (day_05::part1, Some(day_05::part2)),
+    (day_06::part1, Some(day_06::part2)),
+    (day_07::part1, Some(day_07::part2)),
+    (day_08::part1, Some(day_08::part2)),
+    (day_09::part1, Some(day_09::part2)),
+    (day_10::part1, Some(day_10::part2)),
+    (day_11::part1, Some(day_11::part2)),
+    (day_12::part1, Some(day_12::part2)),
+    (day_13::part1, Some(day_13::part2)),
+    (day_14::part1, Some(day_14::part2)),
+    (day_15::part1, Some(day_15::part2)),
+    (day_16::part1, Some(day_16::part2)),
+    (day_17::part1, Some(day_17::part2)),
+    (day_18::part1, Some(day_18::part2)),
+    (day_19::part1, Some(day_19::part2)),
+    (day_20::part1, Some(day_20::part2)),
+    (day_21::part1, Some(day_21::part2)),
+    (day_22::part1, Some(day_22::part2)),
+    (day_23::part1, Some(day_23::part2)),
+    (day_24::part1, Some(day_24::part2)),
+    (day_25::part1, None),
+];
@@ -1,5 +1,466 @@
-mod day_25;
+use aoc_2023::error::Context;
+use aoc_2023::store::{self, Name, Status, Store};
+use aoc_2023::verify::{self, Outcome};
+use aoc_2023::{bench, input, pool, render, report, Part, DAYS};
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage:
+    aoc run --day <DAY> [--part <PART>] [--input <PATH>] [--jobs <N>] [--format <FORMAT>]
+    aoc run --all [--jobs <N>] [--format <FORMAT>]
+    aoc bench (--day <DAY> [--part <PART>] [--input <PATH>] | --all) [--runs <N>]
+    aoc verify [--answers <PATH>] [--jobs <N>]
+    aoc render --day <DAY> [--input <PATH>] [--format <FORMAT>] [--output <PATH>] [--scale <N>]
+    aoc inputs [--update]
+
+Commands:
+    run              Print the answers and how long each part took
+    bench            Time parsing and solving separately over several runs
+    verify           Check the answers for the real inputs against the expected ones
+    render           Draw the map of a day (10, 14, 16 or 23) with its solution highlighted
+    inputs           List the inputs in data/ with their checksum status and answers
+
+Options:
+    --day <DAY>      Day to run (1-25)
+    --part <PART>    Part to run (1 or 2); runs both parts if omitted
+    --input <PATH>   Input file, '-' for stdin, or an input name: 'input' (the default)
+                     for data/day_XX_input.txt or 'example:K' for data/day_XX_input_0K.txt
+    --all            Run every day and part in order
+    --jobs <N>       Number of parts to run in parallel; defaults to the number of CPUs
+    --format <FORMAT>
+                     Output format of the answers: 'text' (default) or 'json';
+                     when rendering, the image format: 'ppm' (default) or 'svg'
+    --runs <N>       Number of runs of each part when benchmarking; defaults to 10
+    --answers <PATH> Answers file with lines '<day> <part> <answer>'; defaults to the
+                     answers recorded in data/inputs.txt
+    --output <PATH>  Image file to write, or '-' for stdout; defaults to day_XX.<FORMAT>
+    --scale <N>      Size of each tile in pixels when rendering; defaults to 4
+    --update         Record the current checksums of the inputs in data/inputs.txt";
+
+/// Directory holding the puzzle inputs and their manifest.
+const DATA: &str = "data";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Bench,
+    Verify,
+    Render,
+    Inputs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+    Ppm,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+struct Args {
+    command: Command,
+    day: Option<usize>,
+    part: Option<usize>,
+    input: Option<String>,
+    all: bool,
+    runs: usize,
+    jobs: usize,
+    format: Format,
+    answers: Option<String>,
+    image: ImageFormat,
+    output: Option<String>,
+    scale: usize,
+    update: bool,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Args, String> {
+        let command = match args.first().map(String::as_str) {
+            Some("run") => Command::Run,
+            Some("bench") => Command::Bench,
+            Some("verify") => Command::Verify,
+            Some("render") => Command::Render,
+            Some("inputs") => Command::Inputs,
+            Some(cmd) => return Err(format!("Unknown command: '{cmd}'")),
+            None => return Err(String::from("Missing command")),
+        };
+        let mut out = Args {
+            command,
+            day: None,
+            part: None,
+            input: None,
+            all: false,
+            runs: 10,
+            jobs: pool::default_workers(),
+            format: Format::Text,
+            answers: None,
+            image: ImageFormat::Ppm,
+            output: None,
+            scale: 4,
+            update: false,
+        };
+        let mut args = args[1..].iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for '{arg}'"))
+            };
+            let verify = command == Command::Verify;
+            let render = command == Command::Render;
+            let days = !verify && command != Command::Inputs;
+            match arg.as_str() {
+                "--day" if days => out.day = Some(parse_in_range(value()?, 1..=25, "day")?),
+                "--part" if days && !render => {
+                    out.part = Some(parse_in_range(value()?, 1..=2, "part")?)
+                }
+                "--input" if days => out.input = Some(value()?.clone()),
+                "--all" if days && !render => out.all = true,
+                "--runs" if command == Command::Bench => {
+                    out.runs = parse_in_range(value()?, 1..=usize::MAX, "number of runs")?
+                }
+                "--jobs" if command == Command::Run || verify => {
+                    out.jobs = parse_in_range(value()?, 1..=usize::MAX, "number of jobs")?
+                }
+                "--format" if command == Command::Run => {
+                    out.format = match value()?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => return Err(format!("Invalid format: '{other}'")),
+                    }
+                }
+                "--format" if render => {
+                    out.image = match value()?.as_str() {
+                        "ppm" => ImageFormat::Ppm,
+                        "svg" => ImageFormat::Svg,
+                        other => return Err(format!("Invalid format: '{other}'")),
+                    }
+                }
+                "--output" if render => out.output = Some(value()?.clone()),
+                "--scale" if render => out.scale = parse_in_range(value()?, 1..=64, "scale")?,
+                "--answers" if verify => out.answers = Some(value()?.clone()),
+                "--update" if command == Command::Inputs => out.update = true,
+                _ => return Err(format!("Unknown argument: '{arg}'")),
+            }
+        }
+        if command == Command::Verify || command == Command::Inputs {
+            return Ok(out);
+        }
+        if command == Command::Render && out.day.is_none() {
+            return Err(String::from("'--day' is required"));
+        }
+        match (out.all, out.day) {
+            (true, Some(_)) => Err(String::from("'--all' cannot be combined with '--day'")),
+            (true, None) if out.part.is_some() || out.input.is_some() => Err(String::from(
+                "'--all' cannot be combined with '--part' or '--input'",
+            )),
+            (false, None) => Err(String::from("Either '--day' or '--all' is required")),
+            _ => Ok(out),
+        }
+    }
+}
+
+fn parse_in_range(
+    s: &str,
+    range: std::ops::RangeInclusive<usize>,
+    what: &str,
+) -> Result<usize, String> {
+    s.parse()
+        .ok()
+        .filter(|x| range.contains(x))
+        .ok_or_else(|| format!("Invalid {what}: '{s}'"))
+}
+
+/// Path of the input of `day` selected by `--input`, warning on stderr if it is the real
+/// input and that is missing or has been modified.
+fn input_path(store: &Store, day: usize, input: Option<&str>) -> String {
+    let path = store.resolve(day, input.unwrap_or("input"));
+    if path == store.path(day, Name::Input) {
+        if let Some(warning) = store.warning(day) {
+            eprintln!("warning: {warning}");
+        }
+    }
+    path
+}
+
+/// Name of the input in error messages.
+fn file_name(path: &str) -> &str {
+    if path == "-" {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+/// Reads the puzzle text from a file, or from stdin if `path` is `-`.
+fn read_input(path: &str) -> aoc_2023::Result<String> {
+    if path == "-" {
+        input::read_all(io::stdin().lock()).file(file_name(path))
+    } else {
+        input::read_to_string(path)
+    }
+}
+
+/// Benchmarks a single part, printing its timings or error; returns whether it succeeded.
+fn bench_part(day: usize, part: usize, solve: Part, text: &str, file: &str, runs: usize) -> bool {
+    match bench::bench(solve, text, runs).file(file) {
+        Ok(report) => {
+            println!(
+                "Day {day:02} / Part {part} | {} | {}",
+                report.parse, report.solve
+            );
+            true
+        }
+        Err(err) => {
+            eprintln!("Day {day:02} / Part {part}: error: {err}");
+            false
+        }
+    }
+}
+
+/// Renders the map of a day to an image file; returns whether it succeeded.
+fn render_day(args: &Args, store: &Store) -> bool {
+    let day = args.day.unwrap();
+    let Some(render) = render::renderer(day) else {
+        let days: Vec<_> = render::DAYS.iter().map(|(d, _)| d.to_string()).collect();
+        eprintln!(
+            "Day {day} cannot be rendered; try one of {}",
+            days.join(", ")
+        );
+        process::exit(2);
+    };
+    let path = input_path(store, day, args.input.as_deref());
+    let scene = match read_input(&path).and_then(|text| render(&text).file(file_name(&path))) {
+        Ok(scene) => scene,
+        Err(err) => {
+            eprintln!("Day {day:02}: error: {err}");
+            return false;
+        }
+    };
+    let image = match args.image {
+        ImageFormat::Ppm => scene.to_ppm(args.scale),
+        ImageFormat::Svg => scene.to_svg(args.scale).into_bytes(),
+    };
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| format!("day_{day:02}.{}", args.image.extension()));
+    let written = if output == "-" {
+        io::stdout().write_all(&image)
+    } else {
+        fs::write(&output, &image)
+    };
+    match written {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("{}: error: {err}", file_name(&output));
+            false
+        }
+    }
+}
+
+/// Checks every answer in the answers file, or those recorded in the store if there is
+/// none, printing a pass/fail table; returns whether all of them passed.
+fn verify_all(answers: Option<&str>, store: &Store, jobs: usize) -> bool {
+    let answers = match answers {
+        Some(path) => verify::read_answers(path).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            process::exit(2);
+        }),
+        None => store.expected(),
+    };
+    let days: BTreeSet<_> = answers.iter().map(|expected| expected.day).collect();
+    for day in days {
+        if let Some(warning) = store.warning(day) {
+            eprintln!("warning: {warning}");
+        }
+    }
+    println!(
+        "{:15} | {:6} | {:>20} | {:>20}",
+        "", "status", "expected", "actual"
+    );
+    let (mut passed, total) = (0, answers.len());
+    let check = |expected: verify::Expected| {
+        verify::check(&expected, &store.path(expected.day, Name::Input))
+    };
+    pool::run_ordered(answers.clone(), jobs, check, |i, outcome| {
+        let expected = &answers[i];
+        let (day, part) = (expected.day, expected.part);
+        let (status, actual) = match outcome {
+            Ok(Outcome::Pass) => ("ok", String::new()),
+            Ok(Outcome::Fail(answer)) => ("FAIL", answer.to_string()),
+            Ok(Outcome::Error(err)) => ("ERROR", err.to_string()),
+            Err(panic) => ("ERROR", format!("panicked: {panic}")),
+        };
+        if status == "ok" {
+            passed += 1;
+        }
+        let line = format!(
+            "Day {day:02} / Part {part} | {status:6} | {:>20} | {actual:>20}",
+            expected.answer
+        );
+        println!("{}", line.trim_end());
+    });
+    println!("{passed} of {total} passed");
+    passed == total
+}
+
+/// Lists the inputs of the store with the state of their checksums, first recording the
+/// current checksums if `update` is set; returns whether every input is unchanged.
+fn list_inputs(store: &mut Store, update: bool) -> bool {
+    if update {
+        let changed = store.update();
+        if let Err(err) = store.save() {
+            eprintln!("error: {err}");
+            return false;
+        }
+        println!("Recorded {changed} checksums in {DATA}/{}", store::MANIFEST);
+    }
+    let mut ok = true;
+    for entry in store.entries() {
+        let status = store.status(entry);
+        ok &= matches!(status, Status::Ok | Status::Unrecorded);
+        let status = match status {
+            Status::Ok => "ok",
+            Status::Unrecorded => "new",
+            Status::Modified(_) => "MODIFIED",
+            Status::Missing => "MISSING",
+        };
+        let answers: Vec<_> = entry
+            .answers
+            .iter()
+            .enumerate()
+            .filter_map(|(part, answer)| Some(format!("{}={}", part + 1, answer.as_ref()?)))
+            .collect();
+        let line = format!(
+            "Day {:02} | {:10} | {status:8} | {}",
+            entry.day,
+            entry.name.to_string(),
+            answers.join(" ")
+        );
+        println!("{}", line.trim_end());
+    }
+    ok
+}
 
 fn main() {
-    day_25::part1("data/day_25_input.txt");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = Args::parse(&args).unwrap_or_else(|err| {
+        eprintln!("{err}\n\n{USAGE}");
+        process::exit(2);
+    });
+    let mut store = Store::open(DATA).unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        process::exit(2);
+    });
+    if args.command == Command::Inputs {
+        if !list_inputs(&mut store, args.update) {
+            process::exit(1);
+        }
+        return;
+    }
+    if args.command == Command::Verify {
+        if !verify_all(args.answers.as_deref(), &store, args.jobs) {
+            process::exit(1);
+        }
+        return;
+    }
+    if args.command == Command::Render {
+        if !render_day(&args, &store) {
+            process::exit(1);
+        }
+        return;
+    }
+    let days = match args.day {
+        Some(day) => day..=day,
+        None => 1..=DAYS.len(),
+    };
+    let mut parts: Vec<(usize, usize, Part, String)> = vec![];
+    for day in days {
+        let path = input_path(&store, day, args.input.as_deref());
+        let (part1, part2) = DAYS[day - 1];
+        match args.part {
+            Some(1) => parts.push((day, 1, part1, path)),
+            Some(_) => match part2 {
+                Some(part2) => parts.push((day, 2, part2, path)),
+                None => {
+                    eprintln!("Day {day} has no part 2");
+                    process::exit(2);
+                }
+            },
+            None => {
+                parts.push((day, 1, part1, path.clone()));
+                if let Some(part2) = part2 {
+                    parts.push((day, 2, part2, path));
+                }
+            }
+        }
+    }
+    if args.command == Command::Bench {
+        let header = format!("{:15} | {:^32} | {:^32}", "", "parse", "solve");
+        println!("{}", header.trim_end());
+        println!(
+            "{:15} | {:>10} {:>10} {:>10} | {:>10} {:>10} {:>10}",
+            "", "min", "median", "max", "min", "median", "max"
+        );
+    }
+    let mut texts = HashMap::new();
+    for (_, _, _, path) in parts.iter() {
+        texts
+            .entry(path.as_str())
+            .or_insert_with(|| read_input(path));
+    }
+    let mut ok = true;
+    match args.command {
+        Command::Run => {
+            let jobs: Vec<_> = parts
+                .iter()
+                .map(|(day, part, solve, path)| {
+                    (*day, *part, *solve, file_name(path), &texts[path.as_str()])
+                })
+                .collect();
+            let mut json = vec![];
+            report::run_all(&jobs, args.jobs, |record| match args.format {
+                Format::Text => ok &= record.print(),
+                Format::Json => {
+                    ok &= record.result.is_ok();
+                    json.push(format!("  {}", record.to_json()));
+                }
+            });
+            if args.format == Format::Json {
+                println!("[\n{}\n]", json.join(",\n"));
+            }
+        }
+        Command::Bench => {
+            for (day, part, solve, path) in parts.iter() {
+                ok &= match &texts[path.as_str()] {
+                    Ok(text) => bench_part(*day, *part, *solve, text, file_name(path), args.runs),
+                    Err(err) => {
+                        eprintln!("Day {day:02} / Part {part}: error: {err}");
+                        false
+                    }
+                };
+            }
+        }
+        Command::Verify | Command::Render | Command::Inputs => unreachable!(),
+    }
+    if !ok {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Greatest common divisor; always non-negative.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        if a <= u64::MAX as u128 && b <= u64::MAX as u128 {
+            // 64-bit division is much faster than 128-bit division.
+            let (mut a, mut b) = (a as u64, b as u64);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            return a as i128;
+        }
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+/// Least common multiple; always non-negative, zero if either argument is zero, and
+/// `None` if it does not fit into an `i128`.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        Some(0)
+    } else {
+        (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+    }
+}
+
+/// Returns `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Inverse of `a` modulo `m` in `0..m`, or `None` if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `a * b` modulo `m`, without overflowing for any `m` that fits into an `i128`.
+pub fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+    let mut out = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            out = add_mod(out, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    out
+}
+
+/// `a + b` modulo `m` for `a` and `b` in `0..m`.
+fn add_mod(a: i128, b: i128, m: i128) -> i128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Solves the system of congruences `x = r (mod m)` for the given `(r, m)` pairs, whose
+/// moduli need not be coprime. Returns the solution `(x, lcm)` with `x` in `0..lcm`,
+/// or `None` if the congruences contradict each other or the combined modulus would
+/// overflow.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut x = 0;
+    let mut m = 1;
+    for &(r1, m1) in congruences {
+        assert!(m1 > 0, "Modulus must be positive");
+        let g = gcd(m, m1);
+        let diff = (r1 - x).rem_euclid(m1);
+        if diff % g != 0 {
+            return None;
+        }
+        // x + m * k = r1 (mod m1) with k = (diff / g) * inv(m / g) (mod m1 / g).
+        let step = m1 / g;
+        let k = mul_mod(diff / g, mod_inverse(m / g, step)?, step);
+        let next = m.checked_mul(step)?;
+        x = add_mod(x, mul_mod(m, k, next), next);
+        m = next;
+    }
+    Some((x, m))
+}
+
+/// Exact fraction kept in lowest terms with a positive denominator. Arithmetic panics
+/// on overflow instead of losing precision.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// The fraction `num / den`; panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "Zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn num(&self) -> i128 {
+        self.num
+    }
+
+    pub fn den(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// `1 / self`; panics if `self` is zero.
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.den, self.num)
+    }
+
+    /// The value as an integer, or `None` if it is not one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(x: i128) -> Rational {
+        Rational { num: x, den: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(x: i64) -> Rational {
+        Rational::from(x as i128)
+    }
+}
+
+fn checked(x: Option<i128>) -> i128 {
+    x.expect("Rational arithmetic overflowed")
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        if self.den == 1 && other.den == 1 {
+            return Rational::from(checked(self.num.checked_add(other.num)));
+        } else if self.is_zero() || other.is_zero() {
+            return if self.is_zero() { other } else { self };
+        }
+        let g = gcd(self.den, other.den);
+        let (a, b) = (self.den / g, other.den / g);
+        let num = checked(
+            self.num
+                .checked_mul(b)
+                .zip(other.num.checked_mul(a))
+                .and_then(|(x, y)| x.checked_add(y)),
+        );
+        Rational::new(num, checked(self.den.checked_mul(b)))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: checked(self.num.checked_neg()),
+            den: self.den,
+        }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        if self.den == 1 && other.den == 1 {
+            return Rational::from(checked(self.num.checked_mul(other.num)));
+        } else if self.is_zero() || other.is_zero() {
+            return Rational::ZERO;
+        }
+        // Cancelling crosswise first keeps the products small.
+        let g1 = gcd(self.num, other.den);
+        let g2 = gcd(other.num, self.den);
+        let num = checked((self.num / g1).checked_mul(other.num / g2));
+        let den = checked((self.den / g2).checked_mul(other.den / g1));
+        Rational::new(num, den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics if `other` is zero.
+    fn div(self, other: Rational) -> Rational {
+        Mul::mul(self, other.recip())
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        let a = checked(self.num.checked_mul(other.den));
+        let b = checked(other.num.checked_mul(self.den));
+        a.cmp(&b)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Solves the linear system `a * x = b` exactly by Gaussian elimination, returning
+/// `None` if `a` is singular.
+pub fn solve<const N: usize>(
+    mut a: [[Rational; N]; N],
+    mut b: [Rational; N],
+) -> Option<[Rational; N]> {
+    for k in 0..N {
+        let pivot = (k..N).find(|&i| !a[i][k].is_zero())?;
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+        for i in k + 1..N {
+            if a[i][k].is_zero() {
+                continue;
+            }
+            let f = a[i][k] / a[k][k];
+            #[allow(clippy::needless_range_loop)]
+            for j in k..N {
+                a[i][j] = a[i][j] - f * a[k][j];
+            }
+            b[i] = b[i] - f * b[k];
+        }
+    }
+    let mut x = [Rational::ZERO; N];
+    for i in (0..N).rev() {
+        let sum = (i + 1..N).fold(b[i], |acc, j| acc - a[i][j] * x[j]);
+        x[i] = sum / a[i][i];
+    }
+    Some(x)
+}
@@ -0,0 +1,63 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of worker threads to use by default.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Message of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => String::from(*message),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+/// Runs `f` on every job using up to `workers` threads and passes the index of each job
+/// with its result to `done` on the calling thread, in the order of the jobs. A job for
+/// which `f` panics gets the panic message as its result instead.
+pub fn run_ordered<T, R, F, D>(jobs: Vec<T>, workers: usize, f: F, mut done: D)
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+    D: FnMut(usize, Result<R, String>),
+{
+    let n = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, n.max(1)) {
+            let tx = tx.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                let job = queue.lock().unwrap().next();
+                match job {
+                    Some((i, job)) => {
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| f(job)));
+                        tx.send((i, result.map_err(panic_message))).unwrap();
+                    }
+                    None => break,
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = HashMap::new();
+        let mut next = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next) {
+                done(next, result);
+                next += 1;
+            }
+        }
+    });
+}
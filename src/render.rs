@@ -0,0 +1,123 @@
+use crate::error::Result;
+use crate::grid::{Grid, Pos};
+use crate::{day_10, day_14, day_16, day_23};
+use std::fmt::Write;
+
+/// Color as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+pub const YELLOW: Rgb = [250, 200, 40];
+pub const GREEN: Rgb = [60, 200, 90];
+pub const RED: Rgb = [230, 60, 50];
+
+const BACKGROUND: Rgb = [20, 20, 40];
+
+/// Builds the scene of a day from its puzzle text.
+pub type Render = fn(&str) -> Result<Scene>;
+
+/// Days that can be rendered.
+pub const DAYS: [(usize, Render); 4] = [
+    (10, day_10::render),
+    (14, day_14::render),
+    (16, day_16::render),
+    (23, day_23::render),
+];
+
+/// Renderer of `day`, if it has one.
+pub fn renderer(day: usize) -> Option<Render> {
+    DAYS.iter()
+        .find(|&&(d, _)| d == day)
+        .map(|&(_, render)| render)
+}
+
+/// Character map with layers of highlighted cells drawn on top of it.
+pub struct Scene {
+    pub map: Grid<u8>,
+    /// Later layers are drawn above earlier ones.
+    pub layers: Vec<(Rgb, Vec<Pos>)>,
+}
+
+impl Scene {
+    pub fn new(map: Grid<u8>) -> Scene {
+        Scene {
+            map,
+            layers: vec![],
+        }
+    }
+
+    /// Adds a layer highlighting `cells` in `color`.
+    pub fn layer(mut self, color: Rgb, cells: impl IntoIterator<Item = Pos>) -> Scene {
+        self.layers.push((color, cells.into_iter().collect()));
+        self
+    }
+
+    /// Color of every cell; highlighted tiles other than `.` are drawn in a lighter shade
+    /// so that the map stays visible underneath.
+    pub fn colors(&self) -> Grid<Rgb> {
+        let mut out = self.map.map(|&b| tile_color(b));
+        for (color, cells) in self.layers.iter() {
+            for &p in cells.iter() {
+                out[p] = if self.map[p] == b'.' {
+                    *color
+                } else {
+                    color.map(|c| c / 2 + 128)
+                };
+            }
+        }
+        out
+    }
+
+    /// Binary PPM image with each cell drawn as a `scale` by `scale` square.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let colors = self.colors();
+        let (h, w) = (colors.height() * scale, colors.width() * scale);
+        let mut out = format!("P6\n{w} {h}\n255\n").into_bytes();
+        for row in colors.rows() {
+            for _ in 0..scale {
+                for color in row {
+                    for _ in 0..scale {
+                        out.extend_from_slice(color);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// SVG image with each cell drawn as a `scale` by `scale` square.
+    pub fn to_svg(&self, scale: usize) -> String {
+        let colors = self.colors();
+        let (h, w) = (colors.height() * scale, colors.width() * scale);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             shape-rendering=\"crispEdges\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+            hex(BACKGROUND)
+        );
+        for ((i, j), &color) in colors.iter() {
+            if color != BACKGROUND {
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{scale}\" height=\"{scale}\" fill=\"{}\"/>",
+                    j * scale,
+                    i * scale,
+                    hex(color)
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn tile_color(b: u8) -> Rgb {
+    match b {
+        b'.' => BACKGROUND,
+        b'#' => [110, 110, 120],
+        _ => [190, 190, 200],
+    }
+}
+
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
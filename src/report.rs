@@ -0,0 +1,110 @@
+use crate::bench::{self, Timing};
+use crate::error::{Context, Error};
+use crate::{pool, Answer, Part, Result};
+
+/// A part to run: day, part, solver, input name and the puzzle text read from it.
+pub type Job<'a> = (usize, usize, Part, &'a str, &'a Result<String>);
+
+/// Outcome of running a single part.
+pub struct Record {
+    pub day: usize,
+    pub part: usize,
+    pub input: String,
+    pub result: Result<(Answer, Timing)>,
+}
+
+impl Record {
+    /// Runs the part of the job once, timing it.
+    pub fn run(&(day, part, solve, input, text): &Job) -> Record {
+        let result = match text {
+            Ok(text) => bench::time(solve, text).file(input),
+            Err(err) => Err(err.clone()),
+        };
+        Record {
+            day,
+            part,
+            input: String::from(input),
+            result,
+        }
+    }
+
+    /// Record of the part of the job when it panicked with `message`.
+    pub fn panicked(&(day, part, _, input, _): &Job, message: &str) -> Record {
+        Record {
+            day,
+            part,
+            input: String::from(input),
+            result: Err(Error::new(format!("panicked: {message}"))).file(input),
+        }
+    }
+
+    /// Prints the answer and running time or the error; returns whether it succeeded.
+    pub fn print(&self) -> bool {
+        let (day, part) = (self.day, self.part);
+        match &self.result {
+            Ok((answer, timing)) => {
+                let time = timing.parse + timing.solve;
+                println!("Day {day:02} / Part {part}: {answer} ({time:.2?})");
+                true
+            }
+            Err(err) => {
+                eprintln!("Day {day:02} / Part {part}: error: {err}");
+                false
+            }
+        }
+    }
+
+    /// Single line JSON object; the answer and error are strings or `null`.
+    pub fn to_json(&self) -> String {
+        let (answer, parse, solve, error) = match &self.result {
+            Ok((answer, timing)) => (
+                json_string(&answer.to_string()),
+                timing.parse.as_nanos().to_string(),
+                timing.solve.as_nanos().to_string(),
+                String::from("null"),
+            ),
+            Err(err) => (
+                String::from("null"),
+                String::from("null"),
+                String::from("null"),
+                json_string(&err.to_string()),
+            ),
+        };
+        format!(
+            "{{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {answer}, \
+             \"parse_ns\": {parse}, \"solve_ns\": {solve}, \"error\": {error}}}",
+            self.day,
+            self.part,
+            json_string(&self.input),
+        )
+    }
+}
+
+/// Quoted and escaped JSON string.
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Runs the jobs using up to `workers` threads and passes their records to `done` in
+/// the order of the jobs; a part that panics is recorded with the panic as its error.
+pub fn run_all(jobs: &[Job], workers: usize, mut done: impl FnMut(Record)) {
+    pool::run_ordered(
+        jobs.to_vec(),
+        workers,
+        |job| Record::run(&job),
+        |i, record| done(record.unwrap_or_else(|panic| Record::panicked(&jobs[i], &panic))),
+    );
+}
@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Distances and predecessor links of the states reached by a search.
+pub struct Paths<S, C> {
+    pub dist: HashMap<S, C>,
+    pub prev: HashMap<S, S>,
+    /// First target state reached, for searches that stop at a target.
+    pub target: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Paths<S, C> {
+    fn new() -> Paths<S, C> {
+        Paths {
+            dist: HashMap::new(),
+            prev: HashMap::new(),
+            target: None,
+        }
+    }
+
+    /// Distance to `s`, or `None` if it was not reached.
+    pub fn distance(&self, s: &S) -> Option<C> {
+        self.dist.get(s).copied()
+    }
+
+    /// Distance to the target, or `None` if no target was reached.
+    pub fn target_distance(&self) -> Option<C> {
+        self.distance(self.target.as_ref()?)
+    }
+
+    /// Number of states reached.
+    pub fn len(&self) -> usize {
+        self.dist.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dist.is_empty()
+    }
+
+    /// Shortest path from one of the sources to `s`, or `None` if it was not reached.
+    pub fn path(&self, s: &S) -> Option<Vec<S>> {
+        self.dist.get(s)?;
+        let mut path = vec![s.clone()];
+        while let Some(p) = self.prev.get(path.last().unwrap()) {
+            path.push(p.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search yielding each reached state with its distance, in order of
+/// increasing distance.
+pub struct Bfs<S, F> {
+    paths: Paths<S, usize>,
+    queue: VecDeque<S>,
+    successors: F,
+}
+
+impl<S, F, I> Bfs<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    /// Search starting from all of `sources` at distance 0.
+    pub fn new(sources: impl IntoIterator<Item = S>, successors: F) -> Bfs<S, F> {
+        let mut paths = Paths::new();
+        let mut queue = VecDeque::new();
+        for s in sources {
+            if !paths.dist.contains_key(&s) {
+                paths.dist.insert(s.clone(), 0);
+                queue.push_back(s);
+            }
+        }
+        Bfs {
+            paths,
+            queue,
+            successors,
+        }
+    }
+
+    /// Distances and predecessors of the states reached so far.
+    pub fn into_paths(self) -> Paths<S, usize> {
+        self.paths
+    }
+}
+
+impl<S, F, I> Iterator for Bfs<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<(S, usize)> {
+        let s0 = self.queue.pop_front()?;
+        let d0 = self.paths.dist[&s0];
+        for s1 in (self.successors)(&s0) {
+            if !self.paths.dist.contains_key(&s1) {
+                self.paths.dist.insert(s1.clone(), d0 + 1);
+                self.paths.prev.insert(s1.clone(), s0.clone());
+                self.queue.push_back(s1);
+            }
+        }
+        Some((s0, d0))
+    }
+}
+
+/// Breadth-first search over all states reachable from `sources`.
+pub fn bfs<S, F, I>(sources: impl IntoIterator<Item = S>, successors: F) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Bfs::new(sources, successors);
+    search.by_ref().for_each(drop);
+    search.into_paths()
+}
+
+/// Dijkstra's algorithm from `sources`, stopping at the first state satisfying `is_target`.
+/// The successor function returns each neighbor together with the cost of moving there.
+pub fn dijkstra<S, C, F, I, T>(
+    sources: impl IntoIterator<Item = S>,
+    successors: F,
+    is_target: T,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    T: FnMut(&S) -> bool,
+{
+    astar(sources, successors, |_| C::default(), is_target)
+}
+
+/// A* search from `sources`, stopping at the first state satisfying `is_target`.
+/// The `heuristic` must never overestimate the remaining cost and must be consistent.
+pub fn astar<S, C, F, I, H, T>(
+    sources: impl IntoIterator<Item = S>,
+    mut successors: F,
+    mut heuristic: H,
+    mut is_target: T,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    T: FnMut(&S) -> bool,
+{
+    let mut paths = Paths::new();
+    let mut heap = BinaryHeap::new();
+    for s in sources {
+        paths.dist.insert(s.clone(), C::default());
+        heap.push(Entry {
+            priority: heuristic(&s),
+            cost: C::default(),
+            state: s,
+        });
+    }
+    while let Some(Entry {
+        cost: c0,
+        state: s0,
+        ..
+    }) = heap.pop()
+    {
+        if paths.dist[&s0] < c0 {
+            continue;
+        }
+        if is_target(&s0) {
+            paths.target = Some(s0);
+            break;
+        }
+        for (s1, w) in successors(&s0) {
+            let c1 = c0 + w;
+            if paths.dist.get(&s1).is_none_or(|&c| c1 < c) {
+                paths.dist.insert(s1.clone(), c1);
+                paths.prev.insert(s1.clone(), s0.clone());
+                heap.push(Entry {
+                    priority: c1 + heuristic(&s1),
+                    cost: c1,
+                    state: s1,
+                });
+            }
+        }
+    }
+    paths
+}
+
+/// Heap entry ordered so that the lowest priority is popped first.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
@@ -0,0 +1,310 @@
+use crate::error::{Context, Error, Result};
+use crate::verify::Expected;
+use crate::{input, DAYS};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Name of the manifest inside the data directory.
+pub const MANIFEST: &str = "inputs.txt";
+
+/// Which of the inputs of a day a file holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Name {
+    /// The real puzzle input, `day_XX_input.txt`.
+    Input,
+    /// The `k`-th example, `day_XX_input_0K.txt`.
+    Example(usize),
+}
+
+impl Name {
+    /// Parses `input`, `example` (the first example) or `example:K`.
+    pub fn parse(s: &str) -> Option<Name> {
+        match s.split_once(':') {
+            None if s == "input" => Some(Name::Input),
+            None if s == "example" => Some(Name::Example(1)),
+            Some(("example", k)) => k.parse().ok().filter(|&k| k > 0).map(Name::Example),
+            _ => None,
+        }
+    }
+
+    /// File name of this input of `day`.
+    pub fn file(&self, day: usize) -> String {
+        match self {
+            Name::Input => format!("day_{day:02}_input.txt"),
+            Name::Example(k) => format!("day_{day:02}_input_{k:02}.txt"),
+        }
+    }
+
+    /// Day and name of an input file name, such as `day_05_input_02.txt`.
+    pub fn from_file(file: &str) -> Option<(usize, Name)> {
+        let rest = file.strip_prefix("day_")?.strip_suffix(".txt")?;
+        let (day, rest) = rest.split_once("_input")?;
+        let day: usize = day.parse().ok().filter(|d| (1..=DAYS.len()).contains(d))?;
+        let name = match rest.strip_prefix('_') {
+            None if rest.is_empty() => Name::Input,
+            Some(k) => Name::Example(k.parse().ok().filter(|&k| k > 0)?),
+            None => return None,
+        };
+        (name.file(day) == file).then_some((day, name))
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Name::Input => write!(f, "input"),
+            Name::Example(k) => write!(f, "example:{k}"),
+        }
+    }
+}
+
+/// Input file known to the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: usize,
+    pub name: Name,
+    /// Checksum recorded in the manifest, if any.
+    pub checksum: Option<u64>,
+    /// Expected answers of both parts, if known.
+    pub answers: [Option<String>; 2],
+}
+
+impl Entry {
+    /// Parses a manifest line such as `day_05_input_01.txt 9f2c41d0e87b5a13 1=35 2=46`,
+    /// where the checksum may be `-` if it has not been recorded yet.
+    pub fn parse(line: &str) -> Result<Entry> {
+        let fields: Vec<_> = line.split_ascii_whitespace().collect();
+        if fields.len() < 2 {
+            return Err(Error::at(
+                line,
+                line,
+                "Expected '<file> <checksum> [<part>=<answer>]...'",
+            ));
+        }
+        let (day, name) = Name::from_file(fields[0]).ok_or_else(|| {
+            Error::at(
+                line,
+                fields[0],
+                format!("Invalid input file: '{}'", fields[0]),
+            )
+        })?;
+        let checksum = match fields[1] {
+            "-" => None,
+            hex => Some(
+                u64::from_str_radix(hex, 16)
+                    .map_err(|_| Error::at(line, hex, format!("Invalid checksum: '{hex}'")))?,
+            ),
+        };
+        let mut answers = [None, None];
+        for field in fields[2..].iter() {
+            let (part, answer) = input::split_once(line, field, "=")?;
+            let part: usize = input::parse(line, part)?;
+            if part != 1 && (part != 2 || DAYS[day - 1].1.is_none()) {
+                return Err(Error::at(line, field, format!("Invalid part: {part}")));
+            }
+            answers[part - 1] = Some(String::from(answer));
+        }
+        Ok(Entry {
+            day,
+            name,
+            checksum,
+            answers,
+        })
+    }
+
+    /// File name of the input.
+    pub fn file(&self) -> String {
+        self.name.file(self.day)
+    }
+}
+
+impl fmt::Display for Entry {
+    /// Formats the entry as a manifest line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file())?;
+        match self.checksum {
+            Some(checksum) => write!(f, " {checksum:016x}")?,
+            None => write!(f, " -")?,
+        }
+        for (part, answer) in self.answers.iter().enumerate() {
+            if let Some(answer) = answer {
+                write!(f, " {}={answer}", part + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// State of an input file compared with the manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// No checksum has been recorded.
+    Unrecorded,
+    /// The file differs from the recorded one; holds its current checksum.
+    Modified(u64),
+    Missing,
+}
+
+/// Index of the input files in a data directory, with their recorded checksums and
+/// expected answers.
+pub struct Store {
+    dir: String,
+    entries: Vec<Entry>,
+}
+
+impl Store {
+    /// Reads the manifest of `dir` (if there is one) and adds any input files found in
+    /// `dir` that it does not list.
+    pub fn open(dir: &str) -> Result<Store> {
+        let manifest = Path::new(dir).join(MANIFEST);
+        let manifest = manifest.to_string_lossy();
+        let mut entries = if Path::new(&*manifest).exists() {
+            let text = input::read_to_string(&manifest)?;
+            let entries = input::parse_lines(&text, |line| {
+                if line.trim().is_empty() || line.starts_with('#') {
+                    Ok(None)
+                } else {
+                    Entry::parse(line).map(Some)
+                }
+            })
+            .file(&manifest)?;
+            entries.into_iter().flatten().collect()
+        } else {
+            vec![]
+        };
+        if let Ok(files) = fs::read_dir(dir) {
+            for file in files.flatten() {
+                let file = file.file_name();
+                let Some((day, name)) = file.to_str().and_then(Name::from_file) else {
+                    continue;
+                };
+                if !entries
+                    .iter()
+                    .any(|e: &Entry| e.day == day && e.name == name)
+                {
+                    entries.push(Entry {
+                        day,
+                        name,
+                        checksum: None,
+                        answers: [None, None],
+                    });
+                }
+            }
+        }
+        entries.sort_by_key(|e| (e.day, e.name));
+        Ok(Store {
+            dir: String::from(dir),
+            entries,
+        })
+    }
+
+    /// All known inputs, ordered by day and name.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Entry of input `name` of `day`.
+    pub fn get(&self, day: usize, name: Name) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.day == day && e.name == name)
+    }
+
+    /// Path of input `name` of `day`, whether or not it exists.
+    pub fn path(&self, day: usize, name: Name) -> String {
+        Path::new(&self.dir)
+            .join(name.file(day))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Path for an `--input` argument: an input name such as `example:2`, or a path.
+    pub fn resolve(&self, day: usize, input: &str) -> String {
+        match Name::parse(input) {
+            Some(name) => self.path(day, name),
+            None => String::from(input),
+        }
+    }
+
+    /// Compares the file of `entry` with its recorded checksum.
+    pub fn status(&self, entry: &Entry) -> Status {
+        match fs::read(self.path(entry.day, entry.name)) {
+            Err(_) => Status::Missing,
+            Ok(bytes) => match (entry.checksum, checksum(&bytes)) {
+                (None, _) => Status::Unrecorded,
+                (Some(old), new) if old == new => Status::Ok,
+                (_, new) => Status::Modified(new),
+            },
+        }
+    }
+
+    /// Warning about the real input of `day` if it is missing or has been modified.
+    pub fn warning(&self, day: usize) -> Option<String> {
+        let path = self.path(day, Name::Input);
+        match self.get(day, Name::Input).map(|e| self.status(e)) {
+            None | Some(Status::Missing) => Some(format!("{path} is missing")),
+            Some(Status::Modified(_)) => Some(format!(
+                "{path} differs from the recorded checksum; its answers may be wrong"
+            )),
+            Some(Status::Ok | Status::Unrecorded) => None,
+        }
+    }
+
+    /// Expected answers of the real inputs.
+    pub fn expected(&self) -> Vec<Expected> {
+        self.entries
+            .iter()
+            .filter(|e| e.name == Name::Input)
+            .flat_map(|e| {
+                e.answers.iter().enumerate().filter_map(|(part, answer)| {
+                    Some(Expected {
+                        day: e.day,
+                        part: part + 1,
+                        answer: answer.clone()?,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Records the current checksum of every existing file; returns the number of
+    /// checksums that changed.
+    pub fn update(&mut self) -> usize {
+        let mut changed = 0;
+        for k in 0..self.entries.len() {
+            let entry = &self.entries[k];
+            let Ok(bytes) = fs::read(self.path(entry.day, entry.name)) else {
+                continue;
+            };
+            let new = Some(checksum(&bytes));
+            if self.entries[k].checksum != new {
+                self.entries[k].checksum = new;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Writes the manifest.
+    pub fn save(&self) -> Result<()> {
+        let path = Path::new(&self.dir).join(MANIFEST);
+        let mut text = String::from(
+            "# Puzzle inputs with their checksums and expected answers.\n\
+             # file checksum [<part>=<answer>]...\n",
+        );
+        for entry in self.entries.iter() {
+            text.push_str(&format!("{entry}\n"));
+        }
+        fs::write(&path, text).map_err(|err| Error {
+            file: path.to_string_lossy().into_owned(),
+            ..Error::new(err.to_string())
+        })
+    }
+}
+
+/// 64-bit FNV-1a hash of the file contents; detects accidental changes, not tampering.
+pub fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
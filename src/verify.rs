@@ -0,0 +1,69 @@
+use crate::error::{Context, Error, Result};
+use crate::{input, solve_file, Answer, DAYS};
+
+/// Expected answer of a puzzle part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+}
+
+impl Expected {
+    /// Parses a line such as `17 2 1055`.
+    pub fn parse(line: &str) -> Result<Expected> {
+        let fields: Vec<_> = line.split_ascii_whitespace().collect();
+        if fields.len() != 3 {
+            return Err(Error::at(line, line, "Expected '<day> <part> <answer>'"));
+        }
+        let day = input::parse(line, fields[0])?;
+        let part = input::parse(line, fields[1])?;
+        if !(1..=DAYS.len()).contains(&day) {
+            return Err(Error::at(line, fields[0], format!("Invalid day: {day}")));
+        }
+        if part != 1 && (part != 2 || DAYS[day - 1].1.is_none()) {
+            return Err(Error::at(line, fields[1], format!("Invalid part: {part}")));
+        }
+        Ok(Expected {
+            day,
+            part,
+            answer: String::from(fields[2]),
+        })
+    }
+}
+
+/// Reads an answers file, skipping blank lines and `#` comments.
+pub fn read_answers(path: &str) -> Result<Vec<Expected>> {
+    let text = input::read_to_string(path)?;
+    let answers = input::parse_lines(&text, |line| {
+        if line.trim().is_empty() || line.starts_with('#') {
+            Ok(None)
+        } else {
+            Expected::parse(line).map(Some)
+        }
+    })
+    .file(path)?;
+    Ok(answers.into_iter().flatten().collect())
+}
+
+/// Outcome of checking a single part.
+pub enum Outcome {
+    Pass,
+    Fail(Answer),
+    Error(Error),
+}
+
+/// Runs the part on the input file and compares its answer with the expected one.
+pub fn check(expected: &Expected, path: &str) -> Outcome {
+    let (part1, part2) = DAYS[expected.day - 1];
+    let solve = if expected.part == 1 {
+        part1
+    } else {
+        part2.unwrap()
+    };
+    match solve_file(solve, path) {
+        Ok(answer) if answer.to_string() == expected.answer => Outcome::Pass,
+        Ok(answer) => Outcome::Fail(answer),
+        Err(err) => Outcome::Error(err),
+    }
+}
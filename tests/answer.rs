@@ -0,0 +1,9 @@
+use aoc_2023::Answer;
+
+#[test]
+fn large_unsigned() {
+    assert_eq!(Answer::from(42u128), Answer::Int(42));
+    assert_eq!(Answer::from(i128::MAX as u128), Answer::Int(i128::MAX));
+    let max = Answer::from(u128::MAX);
+    assert_eq!(max.to_string(), u128::MAX.to_string());
+}
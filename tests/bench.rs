@@ -0,0 +1,31 @@
+use aoc_2023::bench::{self, Stats};
+use aoc_2023::day_01;
+use std::time::Duration;
+
+#[test]
+fn stats() {
+    let ms = |v: &[u64]| {
+        v.iter()
+            .map(|&x| Duration::from_millis(x))
+            .collect::<Vec<_>>()
+    };
+    let stats = Stats::new(&ms(&[5, 1, 3]));
+    assert_eq!(
+        (stats.min, stats.median, stats.max),
+        (
+            Duration::from_millis(1),
+            Duration::from_millis(3),
+            Duration::from_millis(5)
+        )
+    );
+    let stats = Stats::new(&ms(&[4, 1, 2, 8]));
+    assert_eq!(stats.median, Duration::from_millis(3));
+}
+
+#[test]
+fn report() {
+    let text = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+    let report = bench::bench(day_01::part1, text, 3).unwrap();
+    assert_eq!(report.answer.to_string(), "142");
+    assert!(report.parse.min <= report.parse.median && report.parse.median <= report.parse.max);
+}
@@ -0,0 +1,83 @@
+use std::process::Command;
+
+fn aoc(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn json() {
+    let (ok, out) = aoc(&[
+        "run",
+        "--day",
+        "2",
+        "--input",
+        "data/day_02_input_01.txt",
+        "--format",
+        "json",
+    ]);
+    assert!(ok);
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!((lines[0], lines[3]), ("[", "]"));
+    assert!(lines[1].starts_with(
+        r#"  {"day": 2, "part": 1, "input": "data/day_02_input_01.txt", "answer": "8", "parse_ns": "#
+    ));
+    assert!(lines[1].ends_with(r#", "error": null},"#));
+    assert!(lines[2].contains(r#""answer": "2286""#));
+}
+
+#[test]
+fn json_error() {
+    let (ok, out) = aoc(&[
+        "run",
+        "--day",
+        "2",
+        "--part",
+        "1",
+        "--input",
+        "missing.txt",
+        "--format",
+        "json",
+    ]);
+    assert!(!ok);
+    assert!(out.contains(
+        r#""answer": null, "parse_ns": null, "solve_ns": null, "error": "missing.txt: "#
+    ));
+}
+
+#[test]
+fn render() {
+    let (ok, out) = aoc(&[
+        "render",
+        "--day",
+        "16",
+        "--input",
+        "data/day_16_input_01.txt",
+        "--format",
+        "svg",
+        "--scale",
+        "2",
+        "--output",
+        "-",
+    ]);
+    assert!(ok);
+    assert!(out.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20""#));
+    let (ok, _) = aoc(&["render", "--day", "5"]);
+    assert!(!ok);
+}
+
+#[test]
+fn input_names() {
+    let (ok, out) = aoc(&["run", "--day", "1", "--part", "2", "--input", "example:2"]);
+    assert!(ok);
+    assert!(out.starts_with("Day 01 / Part 2: 281 ("));
+    let (ok, out) = aoc(&["inputs"]);
+    assert!(ok);
+    assert!(out.contains("Day 05 | example:1  | ok       | 1=35 2=46\n"));
+}
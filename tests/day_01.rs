@@ -0,0 +1,52 @@
+use aoc_2023::day_01::{calibration_sum, Match, Vocabulary, DIGITS, ENGLISH};
+
+#[test]
+fn overlapping_words() {
+    let vocabulary = Vocabulary::new(DIGITS.into_iter().chain(ENGLISH));
+    let c = vocabulary.calibrate("xeightwo").unwrap();
+    assert_eq!(c.value, 82);
+    assert_eq!(
+        (c.first, c.last),
+        (
+            Match {
+                start: 1,
+                end: 6,
+                value: 8
+            },
+            Match {
+                start: 5,
+                end: 8,
+                value: 2
+            }
+        )
+    );
+    let values: Vec<_> = vocabulary.matches("oneightwone").map(|m| m.value).collect();
+    assert_eq!(values, [1, 8, 2, 1]);
+    assert_eq!(vocabulary.calibrate("7").unwrap().value, 77);
+    assert_eq!(vocabulary.calibrate("abc"), None);
+}
+
+#[test]
+fn custom_vocabulary() {
+    let german = [
+        ("null", 0),
+        ("eins", 1),
+        ("zwei", 2),
+        ("drei", 3),
+        ("ei", 9),
+    ];
+    let vocabulary = Vocabulary::new(german);
+    // `ei` also occurs inside `eins`, `zwei` and `drei`, but starts later.
+    let c = vocabulary.calibrate("einszweinull").unwrap();
+    assert_eq!((c.value, c.first.end, c.last.start), (10, 4, 8));
+    let c = vocabulary.calibrate("xdreix").unwrap();
+    assert_eq!((c.first.value, c.last.value, c.last.start), (3, 9, 3));
+    // Of two tokens starting at the same place, the longer one counts.
+    assert_eq!(calibration_sum("einsnull\nnulleins\n", &vocabulary), Ok(11));
+
+    let vocabulary = Vocabulary::new([("zero", 0), ("cinq", 5), ("ünf", 6)]);
+    let c = vocabulary.calibrate("fünfzero").unwrap();
+    assert_eq!((c.first.start, c.first.end, c.value), (1, 5, 60));
+    let err = calibration_sum("zero\nnone\n", &vocabulary).unwrap_err();
+    assert_eq!((err.line, err.message.as_str()), (2, "No digit found"));
+}
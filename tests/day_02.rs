@@ -0,0 +1,50 @@
+use aoc_2023::day_02::{blocked, min_bag, Bag, Game, COLORS};
+use aoc_2023::input;
+
+const GAMES: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+#[test]
+fn queries() {
+    let games = input::parse_lines(GAMES, Game::parse).unwrap();
+    let bag: Bag = COLORS.into_iter().zip([12, 13, 14]).collect();
+    let possible: Vec<_> = games.iter().filter(|g| g.possible(&bag)).collect();
+    assert_eq!(
+        possible.iter().map(|g| g.game_id).collect::<Vec<_>>(),
+        [1, 2, 5]
+    );
+    assert_eq!(games[0].min_bag().power(COLORS), 48);
+    assert_eq!(games[3].blocking(&bag), ["blue", "red"]);
+
+    let min = min_bag(&games);
+    assert_eq!(min, COLORS.into_iter().zip([20, 13, 15]).collect());
+    assert!(games.iter().all(|g| g.possible(&min)));
+    let blocked = blocked(&games, &bag);
+    assert_eq!(blocked.len(), 2);
+    assert_eq!((&blocked["red"], &blocked["blue"]), (&vec![3, 4], &vec![4]));
+}
+
+#[test]
+fn any_colors() {
+    let game = Game::parse("Game 9: 2 teal, 1 dark red; 4 teal").unwrap();
+    let bag: Bag = [("teal", 3), ("dark red", 1), ("red", 5)]
+        .into_iter()
+        .collect();
+    assert_eq!(game.blocking(&bag), ["teal"]);
+    assert_eq!(game.min_bag().get("dark red"), 1);
+    // A color the bag does not have at all.
+    assert!(!Game::parse("Game 1: 1 gold").unwrap().possible(&bag));
+    assert_eq!(game.min_bag().power(COLORS), 0);
+}
+
+#[test]
+fn repeated_color() {
+    // As before, the last count of a color in a subset wins.
+    let bag = Bag::parse("1 blue, 2 red, 3 blue", "1 blue, 2 red, 3 blue").unwrap();
+    assert_eq!((bag.get("blue"), bag.get("red")), (3, 2));
+}
@@ -0,0 +1,39 @@
+use aoc_2023::day_03::Engine;
+
+const SCHEMATIC: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+#[test]
+fn queries() {
+    let engine = Engine::new(SCHEMATIC).unwrap();
+    assert_eq!(
+        engine.query(Some(b'*'), Some(2)),
+        [((1, 3), vec![467, 35]), ((8, 5), vec![755, 598])]
+    );
+    assert_eq!(engine.query(Some(b'*'), Some(1)), [((4, 3), vec![617])]);
+    assert_eq!(engine.query(Some(b'#'), None), [((3, 6), vec![633])]);
+    assert_eq!(engine.query(None, None).len(), 6);
+    assert!(engine.query(Some(b'%'), None).is_empty());
+}
+
+#[test]
+fn adjacency() {
+    let engine = Engine::new("..*\n12.\n.#.\n3..\n").unwrap();
+    let graph = engine.adjacency();
+    assert_eq!(graph.symbols, [((0, 2), b'*'), ((2, 1), b'#')]);
+    // 12 touches both symbols, 3 touches `#` diagonally.
+    assert_eq!(graph.number_symbols, [vec![0, 1], vec![1]]);
+    assert_eq!(graph.symbol_numbers, [vec![0], vec![0, 1]]);
+    assert_eq!(graph.edges(), [(0, 0), (0, 1), (1, 1)]);
+    assert_eq!(engine.query(Some(b'#'), Some(2)), [((2, 1), vec![12, 3])]);
+}
@@ -0,0 +1,29 @@
+use aoc_2023::day_04::{total_cards, Card};
+
+fn cards(wins: &[usize]) -> Vec<Card> {
+    wins.iter()
+        .map(|&num_winning| Card { num_winning })
+        .collect()
+}
+
+#[test]
+fn copies() {
+    assert_eq!(total_cards(&cards(&[4, 2, 2, 1, 0, 0])), Ok(30));
+    assert_eq!(total_cards(&cards(&[])), Ok(0));
+    // Wins reaching past the last card are ignored.
+    assert_eq!(total_cards(&cards(&[5, 5])), Ok(3));
+}
+
+#[test]
+fn many_cards() {
+    // With one win each, card `i` ends up with `i` copies; with ten wins each, the
+    // counts roughly double from card to card and soon overflow.
+    let n = 2_000_000;
+    assert_eq!(
+        total_cards(&cards(&vec![1; n])),
+        Ok((n * (n + 1) / 2) as u64)
+    );
+    let err = total_cards(&cards(&vec![10; n])).unwrap_err();
+    assert!(err.message.starts_with("Number of copies of card "));
+    assert!(err.message.ends_with(" overflows"));
+}
@@ -0,0 +1,84 @@
+use aoc_2023::day_05::{Input, Map, Range};
+use aoc_2023::interval::{Interval, IntervalSet};
+use std::fs;
+use std::ptr;
+
+fn example() -> Input {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/day_05_input_01.txt");
+    Input::parse(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn compose_matches_chain() {
+    let input = example();
+    let map = input.composed();
+    assert_eq!(map.name, "seed-to-location");
+    for x in 0..200 {
+        let chained = input.maps().iter().fold(x, |acc, m| m.apply(acc));
+        assert_eq!(map.apply(x), chained, "x = {x}");
+    }
+    assert!(map
+        .ranges
+        .windows(2)
+        .all(|w| w[0].src_end() <= w[1].src_start));
+    assert!(ptr::eq(map, input.composed()));
+
+    let seeds: IntervalSet = [Interval::new(79, 93), Interval::new(55, 68)]
+        .into_iter()
+        .collect();
+    let chained = input
+        .maps()
+        .iter()
+        .fold(seeds.clone(), |acc, m| m.apply_set(&acc));
+    assert_eq!(map.apply_set(&seeds), chained);
+    assert_eq!(chained.min(), Some(46));
+}
+
+#[test]
+fn intervals_and_printing() {
+    let map = Map {
+        name: String::from("a-to-b"),
+        ranges: vec![
+            Range {
+                dest_start: 50,
+                src_start: 10,
+                src_len: 5,
+            },
+            Range {
+                dest_start: 0,
+                src_start: 20,
+                src_len: 2,
+            },
+        ],
+    };
+    let out = map.apply_interval(Interval::new(12, 21));
+    let expected: IntervalSet = [(0, 1), (15, 20), (52, 55)]
+        .into_iter()
+        .map(|(a, b)| Interval::new(a, b))
+        .collect();
+    assert_eq!(out, expected);
+    assert_eq!(map.to_string(), "a-to-b map:\n50 10 5\n0 20 2\n");
+
+    // `back` undoes the first range, which drops out, and applies to 50..55 on its own.
+    let back = Map {
+        name: String::from("b-to-c"),
+        ranges: vec![Range {
+            dest_start: 10,
+            src_start: 50,
+            src_len: 5,
+        }],
+    };
+    let composed = map.compose(&back);
+    assert_eq!(composed.to_string(), "a-to-c map:\n0 20 2\n10 50 5\n");
+}
+
+#[test]
+fn overlapping_ranges() {
+    let err = Input::parse("seeds: 1\n\na-to-b map:\n1 10 5\n2 12 1\n")
+        .err()
+        .unwrap();
+    assert_eq!(
+        (err.line, err.message.as_str()),
+        (3, "Overlapping source ranges")
+    );
+}
@@ -0,0 +1,23 @@
+use aoc_2023::day_06::{win_count, win_count_brute, win_count_checked};
+
+#[test]
+fn small_races() {
+    for t in 0..60 {
+        for d in 0..t * t / 4 + 3 {
+            assert_eq!(win_count(t, d), win_count_brute(t, d), "t = {t}, d = {d}");
+        }
+    }
+    assert_eq!(win_count_checked(30, 200), Ok(9));
+    assert_eq!(win_count_checked(u64::MAX, 0), Ok(u64::MAX - 1));
+}
+
+#[test]
+fn large_races() {
+    assert_eq!(win_count(71530, 940200), 71503);
+    // Only beaten by holding for exactly half the time, which f64 cannot tell apart.
+    let t = 1 << 32;
+    assert_eq!(win_count(t, (t / 2) * (t / 2) - 1), 1);
+    assert_eq!(win_count(t, (t / 2) * (t / 2)), 0);
+    assert_eq!(win_count(u64::MAX, 0), u64::MAX - 1);
+    assert_eq!(win_count(u64::MAX, u64::MAX), u64::MAX - 3);
+}
@@ -0,0 +1,17 @@
+use aoc_2023::day_17::City;
+
+#[test]
+fn zero_cost_tiles() {
+    // The Manhattan distance overestimates the remaining cost when tiles may cost 0.
+    let city = City::parse("0730\n7017\n0710\n1000\n0090\n").unwrap();
+    assert_eq!(city.search(false), Ok(8));
+}
+
+#[test]
+fn no_path() {
+    // The ultra crucible cannot stop after fewer than 4 blocks.
+    let city = City::parse("12\n").unwrap();
+    assert_eq!(city.search(false), Ok(2));
+    let err = city.search(true).unwrap_err();
+    assert_eq!(err.message, "No path to the bottom-right block");
+}
@@ -0,0 +1,22 @@
+use aoc_2023::day_24::{intersect_xy, Hailstone};
+
+#[test]
+fn exact_intersections() {
+    let h = |s| Hailstone::parse(s).unwrap();
+    let a = h("19, 13, 30 @ -2, 1, -2");
+    assert!(intersect_xy(&a, &h("18, 19, 22 @ -1, -1, -2"), 7, 27));
+    assert!(!intersect_xy(&a, &h("20, 19, 15 @ 1, -5, -3"), 7, 27));
+    // Parallel paths.
+    assert!(!intersect_xy(
+        &h("18, 19, 22 @ -1, -1, -2"),
+        &h("20, 25, 34 @ -2, -2, -4"),
+        7,
+        27
+    ));
+    // Crossing exactly on the far edge of the test area, at x = y = 4e14.
+    let max = 400_000_000_000_000;
+    let a = h("0, 0, 0 @ 1, 1, 0");
+    let b = h("400000000000000, 400000000000001, 0 @ 0, -1, 0");
+    assert!(intersect_xy(&a, &b, 0, max));
+    assert!(!intersect_xy(&a, &b, 0, max - 1));
+}
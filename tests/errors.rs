@@ -0,0 +1,98 @@
+use aoc_2023::{solve_file, solve_reader, DAYS};
+
+/// Runs part 1 of `day` on `input` and returns the error message.
+fn error(day: usize, input: &str) -> String {
+    DAYS[day - 1].0(input).unwrap_err().to_string()
+}
+
+#[test]
+fn reports_position() {
+    let cases = [
+        (
+            2,
+            "Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2\n",
+            "2:17: Expected ' '",
+        ),
+        (
+            10,
+            "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ.x.\n",
+            "5:4: Invalid tile: 'x'",
+        ),
+        (
+            18,
+            "R 6 (#70c710)\nD x (#0dc571)\n",
+            "2:3: Invalid number: 'x'",
+        ),
+        (
+            19,
+            "in{q<5:A,R}\n\n{x=1,m=2,a=3,s=4}\n",
+            "1:4: Invalid category: 'q'",
+        ),
+        (
+            19,
+            "in{é<5:A,R}\n\n{x=1,m=2,a=3,s=4}\n",
+            "1:4: Invalid category: 'é'",
+        ),
+        (
+            20,
+            "broadcaster -> a\n!a -> b\n",
+            "2:1: Invalid module: '!a'",
+        ),
+    ];
+    for (day, input, expected) in cases {
+        assert_eq!(error(day, input), expected, "day {day}");
+    }
+}
+
+#[test]
+fn reports_missing_file() {
+    let err = solve_file(DAYS[0].0, "no/such/file.txt").unwrap_err();
+    assert_eq!(err.file, "no/such/file.txt");
+    assert_eq!(err.line, 0);
+}
+
+#[test]
+fn reports_file() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/day_10_input_01.txt");
+    let err = solve_file(DAYS[1].0, path).unwrap_err();
+    assert_eq!(err.to_string(), format!("{path}:1:1: Expected 'Game '"));
+}
+
+#[test]
+fn reads_from_reader() {
+    let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2\n";
+    let err = solve_reader(DAYS[1].0, input.as_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "2:17: Expected ' '");
+    let answer = solve_reader(DAYS[1].0, "Game 7: 3 blue, 4 red\n".as_bytes()).unwrap();
+    assert_eq!(answer.to_string(), "7");
+}
+
+#[test]
+fn rejects_unsolvable_input() {
+    assert_eq!(error(18, ""), "Empty dig plan");
+    assert_eq!(
+        error(18, "R 6 (#70c710)\n"),
+        "1: Dig plan ends at (0, 6) instead of the starting point"
+    );
+    assert_eq!(
+        error(18, "R 2 (#000000)\nL 2 (#000000)\n"),
+        "1: Step doubles back on the one before"
+    );
+    assert_eq!(
+        error(18, "R 2 (#000000)\nR 0 (#000000)\n"),
+        "2:3: Invalid length: 0"
+    );
+    let crossing = "R 4 (#0)\nD 4 (#0)\nL 2 (#0)\nU 6 (#0)\nL 2 (#0)\nD 2 (#0)\n";
+    assert_eq!(error(18, crossing), "Dig plan crosses itself in row 0");
+    assert_eq!(
+        error(19, "in{a<2:px,A}\npx{s>5:in,R}\n\n{x=1,m=2,a=3,s=4}\n"),
+        "Workflow 'in' leads back to itself"
+    );
+    assert_eq!(error(8, "L\n\nAAA = (AAA, AAA)\n"), "Missing node 'ZZZ'");
+    assert_eq!(
+        error(8, "L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n"),
+        "Node 'ZZZ' cannot be reached from 'AAA'"
+    );
+    let err = DAYS[12].1.unwrap()("#\n").unwrap_err();
+    assert_eq!(err.to_string(), "No smudge gives a new reflection line");
+}
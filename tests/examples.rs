@@ -0,0 +1,45 @@
+use aoc_2023::store::{Name, Store};
+use aoc_2023::{solve_file, DAYS};
+use std::panic;
+
+fn run(day: usize, part: usize, path: &str) -> Result<String, String> {
+    let (part1, part2) = DAYS[day - 1];
+    let solve = match part {
+        1 => part1,
+        _ => part2.ok_or("no such part")?,
+    };
+    panic::catch_unwind(|| solve_file(solve, path))
+        .map_err(|_| String::from("panicked"))?
+        .map(|answer| answer.to_string())
+        .map_err(|err| err.to_string())
+}
+
+#[test]
+fn examples() {
+    let store = Store::open(concat!(env!("CARGO_MANIFEST_DIR"), "/data")).unwrap();
+    let mut total = 0;
+    let mut failures = vec![];
+    for e in store.entries().iter().filter(|e| e.name != Name::Input) {
+        for (part, expected) in e.answers.iter().enumerate() {
+            let Some(expected) = expected else {
+                continue;
+            };
+            total += 1;
+            let (day, part, file) = (e.day, part + 1, e.file());
+            match run(day, part, &store.path(day, e.name)) {
+                Ok(actual) if &actual == expected => {}
+                Ok(actual) => failures.push(format!(
+                    "day {day} part {part} on {file}: expected {expected}, got {actual}"
+                )),
+                Err(err) => failures.push(format!("day {day} part {part} on {file}: {err}")),
+            }
+        }
+    }
+    assert!(total > 0);
+    assert!(
+        failures.is_empty(),
+        "{} of {total} examples failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
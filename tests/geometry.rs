@@ -0,0 +1,25 @@
+use aoc_2023::geometry::{Direction, Point};
+
+#[test]
+fn turns() {
+    for d in Direction::ALL {
+        assert_eq!(d.turn_left().turn_right(), d);
+        assert_eq!(d.turn_left().turn_left(), d.reverse());
+        assert_eq!(d.delta() + d.reverse().delta(), Point::default());
+    }
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::from_letter('L'), Some(Direction::Left));
+    assert_eq!(Direction::from_letter('x'), None);
+}
+
+#[test]
+fn points() {
+    let p = Point::new(2, -3);
+    assert_eq!(p.step(Direction::Up), Point::new(1, -3));
+    assert_eq!(p.step_by(Direction::Right, 5), Point::new(2, 2));
+    assert_eq!(p.manhattan(Point::new(-1, 1)), 7);
+    assert_eq!(p.pos(), None);
+    assert_eq!(Point::from((4, 5)).pos(), Some((4, 5)));
+    assert_eq!(Direction::Left.step((3, 0)), None);
+    assert_eq!(Direction::Down.step((3, 0)), Some((4, 0)));
+}
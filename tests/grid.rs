@@ -0,0 +1,55 @@
+use aoc_2023::geometry::{Direction, Point};
+use aoc_2023::grid::Grid;
+
+fn grid() -> Grid<u8> {
+    Grid::parse("abc\ndef\n", b"abcdef").unwrap()
+}
+
+fn text(grid: &Grid<u8>) -> String {
+    let rows: Vec<_> = grid
+        .rows()
+        .map(|r| String::from_utf8(r.to_vec()).unwrap())
+        .collect();
+    rows.join("\n")
+}
+
+#[test]
+fn parse() {
+    let g = grid();
+    assert_eq!((g.height(), g.width()), (2, 3));
+    assert_eq!(g[(1, 2)], b'f');
+    assert_eq!(g.get((2, 0)), None);
+    assert_eq!(g.get_point(Point::new(-1, 0)), None);
+    let err = Grid::parse("ab\nax\n", b"ab").unwrap_err();
+    assert_eq!(err.to_string(), "2:2: Invalid tile: 'x'");
+    let err = Grid::parse("ab\na\n", b"ab").unwrap_err();
+    assert_eq!(err.to_string(), "2:2: Expected 2 tiles, found 1");
+}
+
+#[test]
+fn views() {
+    let g = grid();
+    assert_eq!(g.row(1), b"def");
+    assert_eq!(g.col(1).copied().collect::<Vec<_>>(), b"be");
+    assert_eq!(g.cols().count(), 3);
+    assert_eq!(text(&g.transposed()), "ad\nbe\ncf");
+    assert_eq!(text(&g.rotated_cw()), "da\neb\nfc");
+    assert_eq!(text(&g.rotated_ccw()), "cf\nbe\nad");
+    assert_eq!(g.rotated_cw().rotated_ccw(), g);
+    assert_eq!(g.find(&b'e'), Some((1, 1)));
+    assert_eq!(g.find(&b'z'), None);
+}
+
+#[test]
+fn neighbors() {
+    let g = grid();
+    assert_eq!(g.neighbors4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    assert_eq!(g.neighbors4((1, 1)).count(), 3);
+    assert_eq!(
+        g.neighbors8((0, 1)).collect::<Vec<_>>(),
+        [(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+    );
+    assert_eq!(g.step((0, 2), Direction::Down), Some((1, 2)));
+    assert_eq!(g.step((0, 2), Direction::Right), None);
+    assert_eq!(g.step((0, 0), Direction::Up), None);
+}
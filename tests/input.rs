@@ -0,0 +1,61 @@
+use aoc_2023::input;
+
+#[test]
+fn numbers() {
+    let line = "seeds: 79 14  55";
+    assert_eq!(
+        input::numbers::<u64>(line, &line[7..]).unwrap(),
+        [79, 14, 55]
+    );
+    let err = input::numbers::<u64>(line, "79 x").unwrap_err();
+    assert_eq!(err.message, "Invalid number: 'x'");
+    let line = "19, 13, 30 @ -2,  1, -2";
+    assert_eq!(
+        input::ints::<i64>(line, line).unwrap(),
+        [19, 13, 30, -2, 1, -2]
+    );
+    assert_eq!(
+        input::ints::<i32>("x=-5..y=12", "x=-5..y=12").unwrap(),
+        [-5, 12]
+    );
+    let err = input::ints::<u8>("1,300", "1,300").unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (3, "Invalid number: '300'")
+    );
+}
+
+#[test]
+fn headers_and_records() {
+    assert_eq!(input::header("Time:      7  15", "Time").unwrap(), "7  15");
+    let err = input::header("Distance: 9", "Time").unwrap_err();
+    assert_eq!(err.message, "Expected 'Time:'");
+    let (id, body) = input::record::<u32>("Card  12: 41 48 | 83", "Card").unwrap();
+    assert_eq!((id, body), (12, "41 48 | 83"));
+    let err = input::record::<u32>("Cards 1: 2", "Card").unwrap_err();
+    assert_eq!(err.message, "Expected 'Card '");
+    let err = input::record::<u32>("Card x: 2", "Card").unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (6, "Invalid number: 'x'")
+    );
+}
+
+#[test]
+fn blocks() {
+    let text = "a\nb\n\n\nc\n  \nd: 1\n2\n";
+    let blocks = input::blocks(text);
+    let lines: Vec<_> = blocks.iter().map(|b| (b.line, b.lines.clone())).collect();
+    assert_eq!(
+        lines,
+        [(1, vec!["a", "b"]), (5, vec!["c"]), (7, vec!["d: 1", "2"])]
+    );
+    let (header, rest) = blocks[2].split_first();
+    assert_eq!(header, "d: 1");
+    assert_eq!(rest.line, 8);
+    assert_eq!(rest.parse_lines(|l| input::parse::<u8>(l, l)).unwrap(), [2]);
+    let err = blocks[2]
+        .parse_lines(|l| input::numbers::<u8>(l, l))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "7:1: Invalid number: 'd:'");
+}
@@ -0,0 +1,76 @@
+use aoc_2023::interval::{Interval, IntervalBox, IntervalSet};
+
+fn set(ints: &[(u64, u64)]) -> IntervalSet {
+    ints.iter().map(|&(a, b)| Interval::new(a, b)).collect()
+}
+
+fn pairs(set: &IntervalSet) -> Vec<(u64, u64)> {
+    set.intervals()
+        .iter()
+        .map(|int| (int.start, int.end))
+        .collect()
+}
+
+#[test]
+fn intervals() {
+    let int = Interval::new(3, 8);
+    assert_eq!(int.len(), 5);
+    assert!(int.contains(3) && !int.contains(8));
+    assert_eq!(int.intersect(&Interval::new(6, 10)), Interval::new(6, 8));
+    assert!(int.intersect(&Interval::new(8, 10)).is_empty());
+    assert_eq!(int.split_at(5), (Interval::new(3, 5), Interval::new(5, 8)));
+    assert_eq!(int.split_at(1), (Interval::new(3, 3), Interval::new(3, 8)));
+    assert_eq!(Interval::new(5, 2).len(), 0);
+}
+
+#[test]
+fn normalizes() {
+    let s = set(&[(5, 7), (1, 3), (3, 4), (6, 9), (12, 12), (20, 21)]);
+    assert_eq!(pairs(&s), [(1, 4), (5, 9), (20, 21)]);
+    assert_eq!(s.count(), 8);
+    assert_eq!(s.min(), Some(1));
+    assert!(s.contains(8) && !s.contains(4) && !s.contains(21));
+    assert!(IntervalSet::new().is_empty());
+}
+
+#[test]
+fn set_operations() {
+    let a = set(&[(0, 10), (20, 30)]);
+    let b = set(&[(5, 25), (28, 40)]);
+    assert_eq!(pairs(&a.union(&b)), [(0, 40)]);
+    assert_eq!(pairs(&a.intersection(&b)), [(5, 10), (20, 25), (28, 30)]);
+    assert_eq!(pairs(&a.difference(&b)), [(0, 5), (25, 28)]);
+    assert_eq!(pairs(&b.difference(&a)), [(10, 20), (30, 40)]);
+    assert_eq!(a.difference(&a), IntervalSet::new());
+    let (yes, no) = a.split_by(|x| x < 25);
+    assert_eq!(pairs(&yes), [(0, 10), (20, 25)]);
+    assert_eq!(pairs(&no), [(25, 30)]);
+    // Brute-force check against explicit sets of integers.
+    let c = set(&[(2, 3), (7, 22), (26, 27)]);
+    let members = |s: &IntervalSet| (0..40).filter(|&x| s.contains(x)).collect::<Vec<_>>();
+    let expected = |f: &dyn Fn(u64) -> bool| (0..40).filter(|&x| f(x)).collect::<Vec<_>>();
+    assert_eq!(
+        members(&a.union(&c)),
+        expected(&|x| a.contains(x) || c.contains(x))
+    );
+    assert_eq!(
+        members(&a.intersection(&c)),
+        expected(&|x| a.contains(x) && c.contains(x))
+    );
+    assert_eq!(
+        members(&a.difference(&c)),
+        expected(&|x| a.contains(x) && !c.contains(x))
+    );
+}
+
+#[test]
+fn boxes() {
+    let b = IntervalBox([Interval::new(1, 4001); 4]);
+    assert_eq!(b.volume(), 4000u128.pow(4));
+    let (lo, hi) = b.split_at(2, 1000);
+    assert_eq!(lo.volume() + hi.volume(), b.volume());
+    assert_eq!(lo.0[2], Interval::new(1, 1000));
+    assert!(lo.contains(&[1, 1, 999, 4000]) && !lo.contains(&[1, 1, 1000, 1]));
+    assert!(lo.intersect(&hi).is_empty());
+    assert_eq!(b.intersect(&lo), lo);
+}
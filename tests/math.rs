@@ -0,0 +1,85 @@
+use aoc_2023::math::{crt, extended_gcd, gcd, lcm, mod_inverse, mul_mod, solve, Rational};
+
+#[test]
+fn divisors() {
+    assert_eq!(gcd(12, -18), 6);
+    assert_eq!(gcd(0, 5), 5);
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(0, 6), Some(0));
+    assert_eq!(lcm(-4, 6), Some(12));
+    assert_eq!(lcm(i128::MAX, i128::MAX - 1), None);
+    for (a, b) in [(240, 46), (-7, 3), (0, 9), (17, 0)] {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, gcd(a, b));
+        assert_eq!(a * x + b * y, g);
+    }
+}
+
+#[test]
+fn mixed_widths() {
+    let big = (1 << 64) + 6;
+    assert_eq!(gcd(6, big), 2);
+    assert_eq!(gcd(big, 6), 2);
+    assert_eq!(lcm(6, big), Some(3 * big));
+    assert_eq!(crt(&[(0, 6), (0, big)]), Some((0, 3 * big)));
+    assert_eq!(Rational::new(6, big), Rational::new(3, big / 2));
+    assert_eq!(Rational::new(6, big).den(), big / 2);
+}
+
+#[test]
+fn inverses() {
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(-3, 11), Some(7));
+    assert_eq!(mod_inverse(6, 9), None);
+    let m = i128::MAX - 1;
+    assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+}
+
+#[test]
+fn chinese_remainder() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    // Moduli sharing factors.
+    assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt(&[(-1, 10), (9, 15)]), Some((9, 30)));
+    assert_eq!(crt(&[]), Some((0, 1)));
+    // Combined modulus beyond 64 bits.
+    let (a, b) = (1_000_000_000_000_000_003, 1_000_000_000_000_000_009);
+    let (x, m) = crt(&[(1, a), (2, b)]).unwrap();
+    assert_eq!(m, a * b);
+    assert_eq!((x % a, x % b), (1, 2));
+    assert_eq!(crt(&[(0, i128::MAX), (1, i128::MAX - 1)]), None);
+}
+
+#[test]
+fn rationals() {
+    let r = |n, d| Rational::new(n, d);
+    assert_eq!(r(6, -4), r(-3, 2));
+    assert_eq!((r(-3, 2).num(), r(-3, 2).den()), (-3, 2));
+    assert_eq!(r(1, 6) + r(1, 3), r(1, 2));
+    assert_eq!(r(1, 2) - r(3, 4), r(-1, 4));
+    assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+    assert_eq!(r(2, 3) / r(-4, 9), r(-3, 2));
+    assert!(r(1, 3) < r(1, 2) && r(-1, 2) < r(-1, 3));
+    assert_eq!(r(8, 4).to_integer(), Some(2));
+    assert_eq!(r(1, 4).to_integer(), None);
+    assert_eq!(r(-7, 3).to_string(), "-7/3");
+    // Products whose intermediate values exceed 64 bits stay exact.
+    let big = r(400_000_000_000_000, 3);
+    assert_eq!(big * big / big, big);
+}
+
+#[test]
+fn linear_systems() {
+    let q = |rows: [[i128; 3]; 3]| rows.map(|row| row.map(Rational::from));
+    let a = q([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+    let b = [8i128, -11, -3].map(Rational::from);
+    assert_eq!(solve(a, b), Some([2i128, 3, -1].map(Rational::from)));
+    // Needs a row swap and has a fractional solution.
+    let a = q([[0, 1, 0], [3, 0, 0], [0, 0, 2]]);
+    let b = [1i128, 1, 1].map(Rational::from);
+    let x = [Rational::new(1, 3), Rational::ONE, Rational::new(1, 2)];
+    assert_eq!(solve(a, b), Some(x));
+    let singular = q([[1, 2, 3], [2, 4, 6], [0, 0, 1]]);
+    assert_eq!(solve(singular, b), None);
+}
@@ -0,0 +1,50 @@
+use aoc_2023::pool;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn results_in_job_order() {
+    for workers in [1, 3, 16] {
+        let jobs: Vec<u64> = (0..20).collect();
+        let mut results = vec![];
+        pool::run_ordered(
+            jobs,
+            workers,
+            |x| {
+                thread::sleep(Duration::from_millis((20 - x) % 7));
+                x * x
+            },
+            |_, r| results.push(r.unwrap()),
+        );
+        assert_eq!(results, (0..20).map(|x| x * x).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn no_jobs() {
+    let mut count = 0;
+    pool::run_ordered(Vec::<u32>::new(), 4, |x| x, |_, _| count += 1);
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn reports_panics() {
+    let mut results = vec![];
+    pool::run_ordered(
+        vec![1, 0, 4],
+        2,
+        |x| {
+            assert!(x > 0, "Job {x} failed");
+            10 / x
+        },
+        |i, r| results.push((i, r)),
+    );
+    assert_eq!(
+        results,
+        [
+            (0, Ok(10)),
+            (1, Err(String::from("Job 0 failed"))),
+            (2, Ok(2))
+        ]
+    );
+}
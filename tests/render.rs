@@ -0,0 +1,47 @@
+use aoc_2023::grid::Grid;
+use aoc_2023::render::{self, Scene, YELLOW};
+use std::collections::HashSet;
+
+fn example(name: &str) -> String {
+    let path = format!("{}/data/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Distinct cells of all layers of the scene rendered for `day`.
+fn highlighted(day: usize, name: &str) -> Vec<usize> {
+    let scene = render::renderer(day).unwrap()(&example(name)).unwrap();
+    scene
+        .layers
+        .iter()
+        .map(|(_, cells)| cells.iter().collect::<HashSet<_>>().len())
+        .collect()
+}
+
+#[test]
+fn highlights_solutions() {
+    // Loop of length 2 * 8 and 10 enclosed tiles.
+    assert_eq!(highlighted(10, "day_10_input_06.txt")[1], 10);
+    assert_eq!(highlighted(16, "day_16_input_01.txt"), [46]);
+    // The hike of 94 steps plus the start.
+    assert_eq!(highlighted(23, "day_23_input_01.txt"), [95]);
+    assert!(render::renderer(5).is_none());
+}
+
+#[test]
+fn writes_images() {
+    let map = Grid::parse(".#\n.O\n", b".#O").unwrap();
+    let scene = Scene::new(map).layer(YELLOW, [(0, 0), (1, 1)]);
+    let colors = scene.colors();
+    assert_eq!(colors[(0, 0)], YELLOW);
+    assert_ne!(colors[(1, 1)], YELLOW);
+    assert_ne!(colors[(1, 0)], colors[(0, 1)]);
+    let ppm = scene.to_ppm(3);
+    let header = b"P6\n6 6\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(ppm.len(), header.len() + 6 * 6 * 3);
+    assert_eq!(&ppm[header.len()..header.len() + 3], &YELLOW);
+    let svg = scene.to_svg(3);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"3\" height=\"3\" fill=\"#fac828\"/>"));
+    assert_eq!(svg.matches("<rect").count(), 4);
+}
@@ -0,0 +1,35 @@
+use aoc_2023::error::Context;
+use aoc_2023::report::{self, Record};
+use aoc_2023::{Answer, Error, Result, DAYS};
+
+fn panicking(_: &str) -> Result<Answer> {
+    panic!("Out of cheese");
+}
+
+#[test]
+fn records_panics() {
+    let text = Ok(String::from("Game 7: 3 blue, 4 red\n"));
+    let missing = Err(Error::new("No such file")).file("b.txt");
+    let jobs = [
+        (2, 1, DAYS[1].0, "a.txt", &text),
+        (
+            2,
+            2,
+            panicking as fn(&str) -> Result<Answer>,
+            "a.txt",
+            &text,
+        ),
+        (2, 2, DAYS[1].1.unwrap(), "b.txt", &missing),
+    ];
+    let mut records: Vec<Record> = vec![];
+    report::run_all(&jobs, 2, |record| records.push(record));
+    let json: Vec<_> = records.iter().map(|r| r.to_json()).collect();
+    assert_eq!(json.len(), 3);
+    assert!(json[0]
+        .starts_with(r#"{"day": 2, "part": 1, "input": "a.txt", "answer": "7", "parse_ns": "#));
+    assert_eq!(
+        json[1],
+        r#"{"day": 2, "part": 2, "input": "a.txt", "answer": null, "parse_ns": null, "solve_ns": null, "error": "a.txt: panicked: Out of cheese"}"#
+    );
+    assert!(json[2].ends_with(r#""error": "b.txt: No such file"}"#));
+}
@@ -0,0 +1,49 @@
+use aoc_2023::search::{self, Bfs};
+
+/// Edges of a small weighted graph: 0 -> 1 -> 3 is cheaper than 0 -> 2 -> 3.
+fn edges(s: &usize) -> Vec<(usize, u32)> {
+    match s {
+        0 => vec![(1, 1), (2, 1)],
+        1 => vec![(3, 1)],
+        2 => vec![(3, 5)],
+        3 => vec![(4, 1)],
+        _ => vec![],
+    }
+}
+
+fn unweighted(s: &usize) -> Vec<usize> {
+    edges(s).into_iter().map(|(t, _)| t).collect()
+}
+
+#[test]
+fn breadth_first() {
+    let paths = search::bfs([0], unweighted);
+    assert_eq!(paths.len(), 5);
+    assert_eq!(paths.distance(&4), Some(3));
+    assert_eq!(paths.path(&4), Some(vec![0, 1, 3, 4]));
+    assert_eq!(paths.path(&7), None);
+    let order: Vec<_> = Bfs::new([0], unweighted).collect();
+    assert_eq!(order, [(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+}
+
+#[test]
+fn multiple_sources() {
+    let paths = search::bfs([2, 3], unweighted);
+    assert_eq!(paths.distance(&4), Some(1));
+    assert_eq!(paths.distance(&0), None);
+    let paths = search::dijkstra([0, 2], edges, |&s| s == 3);
+    assert_eq!(paths.target_distance(), Some(2));
+    assert_eq!(paths.path(&3), Some(vec![0, 1, 3]));
+}
+
+#[test]
+fn shortest_paths() {
+    let paths = search::dijkstra([0], edges, |&s| s == 4);
+    assert_eq!(paths.target, Some(4));
+    assert_eq!(paths.target_distance(), Some(3));
+    assert_eq!(paths.path(&4), Some(vec![0, 1, 3, 4]));
+    let heuristic = |&s: &usize| [3, 2, 2, 1, 0].get(s).copied().unwrap_or(0);
+    let paths = search::astar([0], edges, heuristic, |&s| s == 4);
+    assert_eq!(paths.target_distance(), Some(3));
+    assert!(search::dijkstra([4], edges, |&s| s == 0).target.is_none());
+}
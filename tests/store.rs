@@ -0,0 +1,102 @@
+use aoc_2023::store::{self, Entry, Name, Status, Store};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn names() {
+    assert_eq!(Name::parse("input"), Some(Name::Input));
+    assert_eq!(Name::parse("example"), Some(Name::Example(1)));
+    assert_eq!(Name::parse("example:2"), Some(Name::Example(2)));
+    assert_eq!(Name::parse("example:0"), None);
+    assert_eq!(Name::parse("data/day_01_input.txt"), None);
+    assert_eq!(Name::Example(3).file(7), "day_07_input_03.txt");
+    assert_eq!(
+        Name::from_file("day_07_input_03.txt"),
+        Some((7, Name::Example(3)))
+    );
+    assert_eq!(Name::from_file("day_25_input.txt"), Some((25, Name::Input)));
+    assert_eq!(Name::from_file("day_26_input.txt"), None);
+    assert_eq!(Name::from_file("day_7_input.txt"), None);
+    assert_eq!(Name::Example(2).to_string(), "example:2");
+}
+
+#[test]
+fn entries() {
+    let line = "day_05_input_01.txt 00000000deadbeef 1=35 2=46";
+    let entry = Entry::parse(line).unwrap();
+    assert_eq!(
+        entry,
+        Entry {
+            day: 5,
+            name: Name::Example(1),
+            checksum: Some(0xdeadbeef),
+            answers: [Some(String::from("35")), Some(String::from("46"))],
+        }
+    );
+    assert_eq!(entry.to_string(), line);
+    let entry = Entry::parse("day_01_input_02.txt - 2=281").unwrap();
+    assert_eq!((entry.checksum, entry.answers[0].is_none()), (None, true));
+    let err = Entry::parse("day_25_input.txt - 2=1").unwrap_err();
+    assert_eq!((err.column, err.message.as_str()), (20, "Invalid part: 2"));
+    let err = Entry::parse("day_01_input.txt xyz").unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (18, "Invalid checksum: 'xyz'")
+    );
+}
+
+#[test]
+fn checksum() {
+    assert_eq!(store::checksum(b""), 0xcbf29ce484222325);
+    assert_eq!(store::checksum(b"a"), 0xaf63dc4c8601ec8c);
+}
+
+/// Empty directory for a single test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_store_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn store() {
+    let dir = temp_dir("store");
+    fs::write(dir.join("day_03_input.txt"), "467..114..\n").unwrap();
+    fs::write(dir.join("day_03_input_01.txt"), "...*......\n").unwrap();
+    fs::write(dir.join("notes.txt"), "").unwrap();
+    fs::write(
+        dir.join(store::MANIFEST),
+        "# comment\nday_03_input.txt - 1=4361\nday_09_input.txt - 1=114\n",
+    )
+    .unwrap();
+    let mut store = Store::open(dir.to_str().unwrap()).unwrap();
+    let names: Vec<_> = store.entries().iter().map(|e| (e.day, e.name)).collect();
+    assert_eq!(
+        names,
+        [(3, Name::Input), (3, Name::Example(1)), (9, Name::Input)]
+    );
+    assert_eq!(store.expected().len(), 2);
+    assert_eq!(
+        store.resolve(3, "example:1"),
+        dir.join("day_03_input_01.txt").to_str().unwrap()
+    );
+    assert_eq!(store.resolve(3, "other.txt"), "other.txt");
+    let real = store.get(3, Name::Input).unwrap().clone();
+    assert_eq!(store.status(&real), Status::Unrecorded);
+    assert_eq!(store.warning(3), None);
+    assert!(store.warning(9).unwrap().ends_with("is missing"));
+
+    assert_eq!(store.update(), 2);
+    store.save().unwrap();
+    let store = Store::open(dir.to_str().unwrap()).unwrap();
+    let real = store.get(3, Name::Input).unwrap();
+    assert_eq!(store.status(real), Status::Ok);
+    assert_eq!(real.answers[0].as_deref(), Some("4361"));
+
+    fs::write(dir.join("day_03_input.txt"), "467..115..\n").unwrap();
+    assert!(matches!(store.status(real), Status::Modified(_)));
+    assert!(store.warning(3).unwrap().contains("differs"));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,49 @@
+use aoc_2023::store::Store;
+use aoc_2023::verify::{self, Expected, Outcome};
+use std::path::Path;
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Expected::parse("17 2 1055").unwrap(),
+        Expected {
+            day: 17,
+            part: 2,
+            answer: String::from("1055")
+        }
+    );
+    let err = Expected::parse("25 2 1").unwrap_err();
+    assert_eq!((err.column, err.message.as_str()), (4, "Invalid part: 2"));
+    let err = Expected::parse("1 x 1").unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (3, "Invalid number: 'x'")
+    );
+}
+
+#[test]
+fn recorded_answers() {
+    let store = Store::open(concat!(env!("CARGO_MANIFEST_DIR"), "/data")).unwrap();
+    let answers = store.expected();
+    assert_eq!(answers.len(), 49);
+    assert_eq!(
+        answers[0],
+        Expected {
+            day: 1,
+            part: 1,
+            answer: String::from("53921")
+        }
+    );
+}
+
+#[test]
+fn check() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/day_01_input_01.txt");
+    let path = path.to_str().unwrap();
+    let good = Expected::parse("1 1 142").unwrap();
+    let bad = Expected::parse("1 1 143").unwrap();
+    assert!(matches!(verify::check(&good, path), Outcome::Pass));
+    assert!(
+        matches!(verify::check(&bad, path), Outcome::Fail(answer) if answer.to_string() == "142")
+    );
+}